@@ -1,105 +1,80 @@
-use crate::data::collectors::get_os_release_id;
-use crate::utils::unicode_str_width;
+use crate::ascii::Fit;
+use crate::ascii::logos::{Logo, LOGOS, FALLBACK};
+use crate::config::LogoSize;
+use crate::data::collectors::{get_os_release_id, get_os_release_id_like};
+use crate::utils::{unicode_str_width, pad_to_width};
+use std::env;
 
-pub fn builtin_ascii(_width: usize, distro_override: Option<&str>) -> Vec<String> {
-    let id = if let Some(distro) = distro_override {
-        distro.to_lowercase()
-    } else {
-        get_os_release_id().unwrap_or_default()
+const SIZES: [LogoSize; 3] = [LogoSize::Large, LogoSize::Small, LogoSize::Tiny];
+
+pub fn find_logo(name: &str) -> Option<&'static Logo> {
+    let name = name.to_lowercase();
+    LOGOS.iter().find(|logo| logo.names.contains(&name.as_str()))
+}
+
+// "arch-mini", "debian-small", "ubuntu-tiny" - лого сразу с размером
+fn split_size_suffix(name: &str) -> (&str, Option<LogoSize>) {
+    for (suffix, size) in [
+        ("-mini",  LogoSize::Small),
+        ("-small", LogoSize::Small),
+        ("-tiny",  LogoSize::Tiny),
+    ] {
+        if let Some(base) = name.strip_suffix(suffix) {
+            return (base, Some(size));
+        }
+    }
+    (name, None)
+}
+
+// сначала точный ID, потом по цепочке ID_LIKE - производные получают лого родителя
+pub fn detect_logo() -> Option<&'static Logo> {
+    let Some(id) = get_os_release_id() else {
+        // в termux нет os-release
+        if env::var("TERMUX_VERSION").is_ok() || env::var("ANDROID_ROOT").is_ok() {
+            return find_logo("android");
+        }
+        return None;
     };
 
-    let art: &[&str] = match id.as_str() {
-        "arch" | "endeavouros" => &[
-            "                                     ",
-            "                  -`                 ",
-            "                 .o+`                ",
-            "                `oooo.                ",
-            "               `+oooo:               ",
-            "              `+oooooo:              ",
-            "              -+oooooo+:             ",
-            "            `/:-:++oooo+:            ",
-            "           `/++++/+++++++:           ",
-            "          `/++++++++++++++:          ",
-            "         `/+++rustooooooooo\\`        ",
-            "        ./ooosssso++osssssso+`       ",
-            "       .oossssso-````/ossssss+`      ",
-            "      -osssssso.      :ssssssso.     ",
-            "     :osssssss/        osssso+++.    ",
-            "    /ossssssss/        +ssssooo/-    ",
-            "  `/ossssso+/:-        -:/+osssso+-  ",
-            " `+sso+:-`                 `.-/+oso: ",
-            "`++:.                           `-/+/",
-            ".`                                 ` ",
-            "                                     ",
-        ],
-        "arch-mini" => &[
-            "                  ",
-            "        /\\        ",
-            "       /  \\       ",
-            "      /    \\      ",
-            "     _\\     \\     ",
-            "    /        \\    ",
-            "   /          \\   ",
-            "  /     __   \\_\\  ",
-            " /     /  \\     \\ ",
-            "/__,--'    '--,__\\",
-            "                   ",
-        ],
-        "apple" | "macos" | "macbook" | "yablocoder" => &[
-            "                              ",
-            "                    c.'       ",
-            "                 ,xNMM.       ",
-            "               .OMMMMo        ",
-            "               lMM\"           ",
-            "     .;loddo:.  .olloddol;.   ",
-            "   cKMMMMMMMMMMNWMMMMMMMMMM0: ",
-            " .KMMMMMMMMMMMMMMMMMMMMMMMWd. ",
-            " XMMMMMMMMMMMMMMMMMMMMMMMX.   ",
-            ";MMMMMMMMMMMMMMMMMMMMMMMM:    ",
-            ":MMMMMMMMMMMMMMMMMMMMMMMM:    ",
-            ".MMMMMMMMMMMMMMMMMMMMMMMMX.   ",
-            " kMMMMMMMMMMMMMMMMMMMMMMMMWd. ",
-            " 'XMMMMMMMMMMMMMMMMMMMMMMMMMMk",
-            "  'XMMMMMMMMMMMMMMMMMMMMMMMMK.",
-            "    kMMMMMMMMMMMMMMMMMMMMMMd  ",
-            "     ;KMMMMMMMWXXWMMMMMMMk.   ",
-            "       \"cooc*\"    \"*coo'\"     ",
-            "                              ",
-        ],
-        _ => &[
-            "                           ",
-            "                           ",
-            "                           ",
-            " ⠀⠀⠀⠰⡿⠿⠛⠛⠻⠿⣷           ",
-            "⠀⠀⠀⠀⠀⠀⣀⣄⡀⠀⠀⠀⠀⢀⣀⣀⣤⣄⣀⡀ ",
-            "⠀⠀⠀⠀⠀⢸⣿⣿⣷⠀⠀⠀⠀⠛⠛⣿⣿⣿⡛⠿⠷",
-            "⠀⠀⠀⠀⠀⠘⠿⠿⠋⠀⠀⠀⠀⠀⠀⣿⣿⣿⠇   ",
-            "⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠈⠉⠁    ",
-            "⠀⠀⠀⠀⣿⣷⣄⠀⢶⣶⣷⣶⣶⣤⣀        ",
-            "⠀⠀⠀⠀⣿⣿⣿⠀⠀⠀⠀⠀⠈⠙⠻⠗       ",
-            "⠀⠀⠀⣰⣿⣿⣿⠀⠀⠀⠀⢀⣀⣠⣤⣴⣶⡄     ",
-            "⠀⣠⣾⣿⣿⣿⣥⣶⣶⣿⣿⣿⣿⣿⠿⠿⠛⠃     ",
-            "⢰⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⡄           ",
-            "⢸⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⡁           ",
-            "⠈⢿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⠁           ",
-            "⠀⠀⠛⢿⣿⣿⣿⣿⣿⣿⡿⠟            ",
-            "⠀⠀⠀⠀⠀⠉⠉⠉                 ",
-            "                           ",
-            "                           ",
-            "   why do I need Linux?    ",
-        ],
-        // TODO: больше артов для: EOS, омарчи, кака ос, фрибдсм, бубунту, федор а
+    find_logo(&id)
+        .or_else(|| get_os_release_id_like().iter().find_map(|like| find_logo(like)))
+        .or_else(|| find_logo("linux"))
+}
+
+fn art_size(art: &[&str]) -> (usize, usize) {
+    let width = art.iter().map(|l| unicode_str_width(l)).max().unwrap_or(0);
+    (width, art.len())
+}
+
+// auto: первый вариант от большего к меньшему, который влезает. не влез никто - самый маленький
+fn pick_variant(logo: &Logo, size: LogoSize, fit: &Fit) -> &'static [&'static str] {
+    if size != LogoSize::Auto {
+        return logo.variant(size);
+    }
+
+    SIZES.iter()
+        .map(|&s| logo.variant(s))
+        .find(|art| fit.fits(art_size(art)))
+        .unwrap_or_else(|| logo.variant(LogoSize::Tiny))
+}
+
+pub fn builtin_ascii(width: usize, distro_override: Option<&str>, size: LogoSize, fit: &Fit) -> (Vec<String>, &'static [&'static str]) {
+    let (logo, size) = match distro_override {
+        Some(distro) => {
+            let (name, forced) = split_size_suffix(distro);
+            (find_logo(name), forced.unwrap_or(size))
+        }
+        None => (detect_logo(), size),
     };
+    let logo = logo.unwrap_or(&FALLBACK);
+
+    let art = pick_variant(logo, size, fit);
+    let (max_width, _) = art_size(art);
+    let width = width.max(max_width);
 
-    let max_width = art.iter()
-        .map(|l| unicode_str_width(l))
-        .max().unwrap_or(0);
+    let lines = art.iter()
+        .map(|l| pad_to_width(l, width))
+        .collect();
 
-    art.iter().map(|l| {
-        let s = l.to_string();
-        if unicode_str_width(&s) < max_width {
-            let padding = max_width - unicode_str_width(&s);
-            format!("{}{}", s, " ".repeat(padding))
-        } else { s.clone() }
-    }).collect()
+    (lines, logo.colors)
 }
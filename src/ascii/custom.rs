@@ -0,0 +1,85 @@
+use crate::config::{LogoPosition, Padding};
+use crate::utils::{config_home, expand_home};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SYSTEM_ASCII_DIR: &str = "/usr/share/rushfetch/ascii";
+const FRONT_MATTER: &str = "+++";
+
+// необязательный заголовок арта между строками +++
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ArtMeta {
+    pub color:    Option<String>,
+    pub colors:   Option<Vec<String>>,
+    pub position: Option<LogoPosition>,
+    pub padding:  Option<Padding>,
+}
+
+pub struct ArtFile {
+    pub meta:  ArtMeta,
+    pub lines: Vec<String>,
+}
+
+pub fn art_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = config_home() {
+        dirs.push(home.join("rushfetch/ascii"));
+    }
+    dirs.push(PathBuf::from(SYSTEM_ASCII_DIR));
+    dirs
+}
+
+// ascii.name = "mylogo" -> mylogo, mylogo.txt или mylogo.art в одной из папок
+pub fn find_named_art(name: &str) -> Result<PathBuf, String> {
+    let mut searched = Vec::new();
+
+    for dir in art_dirs() {
+        for file in [name.to_string(), format!("{}.txt", name), format!("{}.art", name)] {
+            let path = dir.join(file);
+            if path.is_file() { return Ok(path); }
+        }
+        searched.push(dir.display().to_string());
+    }
+
+    Err(format!("ascii art '{}' not found in {}", name, searched.join(", ")))
+}
+
+pub fn read_art_file(path: &str) -> Result<ArtFile, String> {
+    read_art_path(&expand_home(path))
+}
+
+pub fn read_art_path(path: &Path) -> Result<ArtFile, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("can't read ascii art {}: {}", path.display(), e))?;
+
+    parse_art(&content)
+        .map_err(|e| format!("invalid ascii art {}: {}", path.display(), e))
+}
+
+pub fn parse_art(content: &str) -> Result<ArtFile, String> {
+    let mut lines = content.lines();
+
+    // без заголовка весь файл - это арт
+    if content.lines().next().map(str::trim_end) != Some(FRONT_MATTER) {
+        return Ok(ArtFile {
+            meta:  ArtMeta::default(),
+            lines: lines.map(String::from).collect(),
+        });
+    }
+
+    lines.next();
+    let header: Vec<&str> = lines.by_ref()
+        .take_while(|l| l.trim_end() != FRONT_MATTER)
+        .collect();
+
+    if header.len() + 1 >= content.lines().count() {
+        return Err(format!("front matter is not closed with '{}'", FRONT_MATTER));
+    }
+
+    let meta = toml::from_str(&header.join("\n"))
+        .map_err(|e| format!("front matter: {}", e))?;
+
+    Ok(ArtFile { meta, lines: lines.map(String::from).collect() })
+}
@@ -0,0 +1,956 @@
+use crate::config::LogoSize;
+
+// встроенные логотипы. names - ID из os-release (и алиасы для ascii.distro),
+// colors - родная палитра для маркеров ${c1}..${c9}
+pub struct Logo {
+    pub names:  &'static [&'static str],
+    pub colors: &'static [&'static str],
+    pub large:  &'static [&'static str],
+    // пустой вариант - берётся следующий по размеру
+    pub small:  &'static [&'static str],
+    pub tiny:   &'static [&'static str],
+}
+
+pub const LOGOS: &[Logo] = &[
+    Logo {
+        names:  &["arch"],
+        colors: &["bright_cyan", "cyan", "bright_red"],
+        large: &[
+            "                                      ",
+            "                  -`                  ",
+            "                 .o+`                 ",
+            "                `oooo.                ",
+            "               `+oooo:                ",
+            "              `+oooooo:               ",
+            "              -+oooooo+:              ",
+            "            `/:-:++oooo+:             ",
+            "           `/++++/+++++++:            ",
+            "          `/++++++++++++++:           ",
+            "         `/+++${c3}rust${c1}ooooooooo\\`         ",
+            "${c2}        ./ooosssso++osssssso+`        ",
+            "       .oossssso-````/ossssss+`       ",
+            "      -osssssso.      :ssssssso.      ",
+            "     :osssssss/        osssso+++.     ",
+            "    /ossssssss/        +ssssooo/-     ",
+            "  `/ossssso+/:-        -:/+osssso+-   ",
+            " `+sso+:-`                 `.-/+oso:  ",
+            "`++:.                           `-/+/ ",
+            ".`                                 `  ",
+            "                                      ",
+        ],
+        small: &[
+            "                   ",
+            "        /\\         ",
+            "       /  \\        ",
+            "      /    \\       ",
+            "     _\\     \\      ",
+            "    /        \\     ",
+            "   /          \\    ",
+            "  /     __   \\_\\   ",
+            " /     /  \\     \\  ",
+            "${c2}/__,--'    '--,__\\ ",
+            "                   ",
+        ],
+        tiny: &[
+            "   /\\   ",
+            "  /  \\  ",
+            " /,--.\\ ",
+            "/_/  \\_\\",
+        ],
+    },
+    Logo {
+        names:  &["endeavouros"],
+        colors: &["red", "magenta", "blue"],
+        large: &[
+            "                     ./${c2}o${c3}.               ",
+            "                   ./${c2}sssso${c3}-             ",
+            "                 `:${c2}osssssss+${c3}-           ",
+            "               `:+${c2}sssssssssso${c3}/.         ",
+            "             `-/o${c2}ssssssssssssso${c3}/.       ",
+            "           `-/+${c2}sssssssssssssssso${c3}+:`     ",
+            "         `-:/+${c2}sssssssssssssssssso${c3}+/.    ",
+            "       `.://o${c2}sssssssssssssssssssso${c3}++-   ",
+            "      .://+${c2}ssssssssssssssssssssssso${c3}++:  ",
+            "    .:///o${c2}ssssssssssssssssssssssssso${c3}++: ",
+            "  `:////${c2}ssssssssssssssssssssssssssso${c3}+++.",
+            "`-////+${c2}ssssssssssssssssssssssssssso${c3}++++-",
+            " `..-+${c2}oosssssssssssssssssssssssso${c3}+++++/`",
+            "   ./++++++++++++++++++++++++++++++/:.  ",
+            "  `:::::::::::::::::::::::::------``    ",
+        ],
+        small: &[
+            "       /${c2}o${c3}.         ",
+            "     /${c2}sssso${c3}-       ",
+            "   /${c2}ossssssso${c3}:     ",
+            "  /${c2}ssssssssssso${c3}+   ",
+            " /${c2}ssssssssssssso${c3}+  ",
+            "//${c2}osssssssssssso${c3}+- ",
+            " `+++++++++++++++-`",
+        ],
+        tiny: &[
+            "   /${c2}o${c3}.  ",
+            "  /${c2}sss${c3}- ",
+            " /${c2}sssss${c3}+",
+            " `-----`",
+        ],
+    },
+    Logo {
+        names:  &["cachyos"],
+        colors: &["bright_cyan", "green"],
+        large: &[
+            "           .-------------------------:                ",
+            "          .+=========================.                ",
+            "         :++===++==================-       ${c2}:++-${c1}       ",
+            "        :*++====+++++=============-        ${c2}.==:${c1}       ",
+            "       -*+++=====+***++==========:                    ",
+            "      =*++++========------------:                     ",
+            "     =*+++++=====-                     ${c2}...${c1}            ",
+            "   .+*+++++=-===:                    ${c2}.=+++=:${c1}          ",
+            "  :++++=====-==:                     ${c2}-*****+${c1}          ",
+            " :++========-=.                      ${c2}.=+**+.${c1}          ",
+            ".+==========-.                          ${c2}.${c1}             ",
+            " :+++++++====-                                ${c2}.--==-.${c1} ",
+            "  :++==========.                             ${c2}:+++++++:${c1}",
+            "   .-===========.                            ${c2}=*****+*+${c1}",
+            "    .-===========:                           ${c2}.+*****+:${c1}",
+            "      -=======++++:::::::::::::::::::::::::-:  ${c2}.---:${c1}  ",
+            "       :======++++====+++******************=.         ",
+            "        :=====+++==========++++++++++++++*-           ",
+            "         .====++==============++++++++++*-            ",
+            "          .===+==================+++++++:             ",
+            "           .-=======================+++:              ",
+            "             ..........................               ",
+        ],
+        small: &[
+            "   /''''''''''''/  ",
+            "  /''''''''''''/   ",
+            " /''''''/        ${c2}o${c1} ",
+            "/''''''/    ${c2}o${c1}      ",
+            "\\......\\      ${c2}O${c1}    ",
+            " \\......\\          ",
+            "  \\.............../",
+            "   \\............./ ",
+        ],
+        tiny: &[
+            "  /'''''/ ",
+            " /''/  ${c2}o${c1}  ",
+            " \\..\\     ",
+            "  \\....../",
+        ],
+    },
+    Logo {
+        names:  &["manjaro", "manjaro-arm"],
+        colors: &["green"],
+        large: &[
+            "██████████████████  ████████",
+            "██████████████████  ████████",
+            "██████████████████  ████████",
+            "██████████████████  ████████",
+            "████████            ████████",
+            "████████  ████████  ████████",
+            "████████  ████████  ████████",
+            "████████  ████████  ████████",
+            "████████  ████████  ████████",
+            "████████  ████████  ████████",
+            "████████  ████████  ████████",
+            "████████  ████████  ████████",
+            "████████  ████████  ████████",
+            "████████  ████████  ████████",
+        ],
+        small: &[
+            "||||||||| ||||",
+            "||||||||| ||||",
+            "||||      ||||",
+            "|||| |||| ||||",
+            "|||| |||| ||||",
+            "|||| |||| ||||",
+            "|||| |||| ||||",
+        ],
+        tiny: &[
+            "███▌▐█",
+            "█▌  ▐█",
+            "█▌▐▌▐█",
+        ],
+    },
+    Logo {
+        names:  &["debian"],
+        colors: &["red", "bright_white"],
+        large: &[
+            "       _,met$$$$$gg.       ",
+            "    ,g$$$$$$$$$$$$$$$P.    ",
+            "  ,g$$P\"     \"\"\"Y$$.\".     ",
+            " ,$$P'              `$$$.  ",
+            "',$$P       ,ggs.     `$$b:",
+            "`d$$'     ,$P\"'   ${c2}.${c1}    $$$ ",
+            " $$P      d$'     ${c2},${c1}    $$P ",
+            " $$:      $$.   ${c2}-${c1}    ,d$$' ",
+            " $$;      Y$b._   _,d$P'   ",
+            " Y$$.    ${c2}`.${c1}`\"Y$$$$P\"'      ",
+            " `$$b      ${c2}\"-.__${c1}           ",
+            "  `Y$$                     ",
+            "   `Y$$.                   ",
+            "     `$$b.                 ",
+            "       `Y$$b.              ",
+            "          `\"Y$b._          ",
+            "              `\"\"\"         ",
+        ],
+        small: &[
+            "  _____  ",
+            " /  __ \\ ",
+            "|  /    |",
+            "|  \\___- ",
+            "-_       ",
+            "  --_    ",
+        ],
+        tiny: &[
+            " ,--. ",
+            "| (_.'",
+            " `-.  ",
+        ],
+    },
+    Logo {
+        names:  &["ubuntu"],
+        colors: &["bright_red", "bright_white"],
+        large: &[
+            "            .-/+oossssoo+/-.            ",
+            "        `:+ssssssssssssssssss+:`        ",
+            "      -+ssssssssssssssssssyyssss+-      ",
+            "    .ossssssssssssssssss${c2}dMMMNy${c1}sssso.    ",
+            "   /sssssssssss${c2}hdmmNNmmyNMMMMh${c1}ssssss/   ",
+            "  +sssssssss${c2}hmydMMMMMMMNddddy${c1}ssssssss+  ",
+            " /ssssssss${c2}hNMMMyhhyyyyhmNMMMNh${c1}ssssssss/ ",
+            ".ssssssss${c2}dMMMNh${c1}ssssssssss${c2}hNMMMd${c1}ssssssss.",
+            "+ssss${c2}hhhyNMMNy${c1}ssssssssssss${c2}yNMMMy${c1}sssssss+",
+            "oss${c2}yNMMMNyMMh${c1}ssssssssssssss${c2}hmmmh${c1}ssssssso",
+            "oss${c2}yNMMMNyMMh${c1}ssssssssssssss${c2}hmmmh${c1}ssssssso",
+            "+ssss${c2}hhhyNMMNy${c1}ssssssssssss${c2}yNMMMy${c1}sssssss+",
+            ".ssssssss${c2}dMMMNh${c1}ssssssssss${c2}hNMMMd${c1}ssssssss.",
+            " /ssssssss${c2}hNMMMyhhyyyyhdNMMMNh${c1}ssssssss/ ",
+            "  +sssssssss${c2}dmydMMMMMMMMddddy${c1}ssssssss+  ",
+            "   /sssssssssss${c2}hdmNNNNmyNMMMMh${c1}ssssss/   ",
+            "    .ossssssssssssssssss${c2}dMMMNy${c1}sssso.    ",
+            "      -+sssssssssssssssss${c2}yyy${c1}ssss+-      ",
+            "        `:+ssssssssssssssssss+:`        ",
+            "            .-/+oossssoo+/-.            ",
+        ],
+        small: &[
+            "         _ ",
+            "     ---(_)",
+            " _/  ---  \\",
+            "(_) |   |  ",
+            "  \\  --- _/",
+            "     ---(_)",
+        ],
+        tiny: &[
+            "  .-o",
+            " o | ",
+            "  `-o",
+        ],
+    },
+    Logo {
+        names:  &["pop"],
+        colors: &["cyan", "bright_white"],
+        large: &[
+            "             /////////////             ",
+            "         /////////////////////         ",
+            "      ///////${c2}*767${c1}////////////////      ",
+            "    //////${c2}7676767676*${c1}//////////////    ",
+            "   /////${c2}76767${c1}//${c2}7676767${c1}//////////////   ",
+            "  /////${c2}767676${c1}///${c2}*76767${c1}///////////////  ",
+            " ///////${c2}767676${c1}///${c2}76767${c1}.///${c2}7676*${c1}/////// ",
+            "/////////${c2}767676${c1}//${c2}76767${c1}///${c2}767676${c1}////////",
+            "//////////${c2}76767676767${c1}////${c2}76767${c1}/////////",
+            "///////////${c2}76767676${c1}//////${c2}7676${c1}//////////",
+            "////////////,${c2}7676${c1},///////${c2}767${c1}///////////",
+            "/////////////*${c2}7676${c1}///////${c2}76${c1}////////////",
+            "///////////////${c2}7676${c1}////////////////////",
+            " ///////////////${c2}7676${c1}///${c2}767${c1}//////////// ",
+            "  //////////////////////${c2}'${c1}////////////  ",
+            "   //////${c2}.7676767676767676767,${c1}//////   ",
+            "    /////${c2}767676767676767676767${c1}/////    ",
+            "      ///////////////////////////      ",
+            "         /////////////////////         ",
+            "             /////////////             ",
+        ],
+        small: &[
+            "______           ",
+            "\\   _ \\        __",
+            " \\ \\ \\ \\      / /",
+            "  \\ \\_\\ \\    / / ",
+            "   \\  ___\\  /_/  ",
+            "    \\ \\    _     ",
+            "   __\\_\\__(_)_   ",
+            "  (___________)  ",
+        ],
+        tiny: &[
+            " __   _ ",
+            " \\_\\ / /",
+            "  \\_\\_/ ",
+            "  (____)",
+        ],
+    },
+    Logo {
+        names:  &["linuxmint", "mint"],
+        colors: &["green", "bright_white"],
+        large: &[
+            "             ...-:::::-...              ",
+            "          .-MMMMMMMMMMMMMMM-.           ",
+            "      .-MMMM${c2}`..-:::::::-..`${c1}MMMM-.       ",
+            "    .:MMMM${c2}.:MMMMMMMMMMMMMMM:.${c1}MMMM:.     ",
+            "   -MMM${c2}-M---MMMMMMMMMMMMMMMMMMM.${c1}MMM-    ",
+            " `:MMM${c2}:MM`  :MMMM:....::-...-MMMM:${c1}MMM:` ",
+            " :MMM${c2}:MMM`  :MM:`  ``    ``  `:MMM:${c1}MMM: ",
+            ".MMM${c2}.MMMM`  :MM.  -MM.  .MM-  `MMMM.${c1}MMM.",
+            ":MMM${c2}:MMMM`  :MM.  -MM-  .MM:  `MMMM-${c1}MMM:",
+            ":MMM${c2}:MMMM`  :MM.  -MM-  .MM:  `MMMM:${c1}MMM:",
+            ":MMM${c2}:MMMM`  :MM.  -MM-  .MM:  `MMMM-${c1}MMM:",
+            ".MMM${c2}.MMMM`  :MM:--:MM:--:MM:  `MMMM.${c1}MMM.",
+            " :MMM${c2}:MMM-  `-MMMMMMMMMMMM-`  -MMM-${c1}MMM: ",
+            "  :MMM${c2}:MMM:`                `:MMM:${c1}MMM:  ",
+            "   .MMM${c2}.MMMM:--------------:MMMM.${c1}MMM.   ",
+            "     '-MMMM${c2}.-MMMMMMMMMMMMMMM-.${c1}MMMM-'    ",
+            "       '.-MMMM${c2}``--:::::--``${c1}MMMM-.'      ",
+            "            '-MMMMMMMMMMMMM-'           ",
+            "               ``-:::::-``              ",
+        ],
+        small: &[
+            " ___________ ",
+            "|_          \\",
+            "  | ${c2}| _____ ${c1}|",
+            "  | ${c2}| | | | ${c1}|",
+            "  | ${c2}| | | | ${c1}|",
+            "  | ${c2}\\_____/ ${c1}|",
+            "  \\_________/",
+        ],
+        tiny: &[
+            " _______",
+            "|_ ${c2}|_|_${c1}|",
+            " |${c2}\\___/${c1}|",
+            " \\_____/",
+        ],
+    },
+    Logo {
+        names:  &["fedora"],
+        colors: &["blue", "bright_white"],
+        large: &[
+            "             .',;::::;,'.             ",
+            "         .';:cccccccccccc:;,.         ",
+            "      .;cccccccccccccccccccccc;.      ",
+            "    .:cccccccccccccccccccccccccc:.    ",
+            "  .;ccccccccccccc;${c2}.:dddl:.${c1};ccccccc;.  ",
+            " .:ccccccccccccc;${c2}OWMKOOXMWd${c1};ccccccc:. ",
+            ".:ccccccccccccc;${c2}KMMc${c1};cc;${c2}xMMc${c1};ccccccc:.",
+            ",cccccccccccccc;${c2}MMM.${c1};cc;${c2};WW:${c1};cccccccc,",
+            ":cccccccccccccc;${c2}MMM.${c1};cccccccccccccccc:",
+            ":ccccccc;${c2}oxOOOo${c1};${c2}MMM0OOk.${c1};cccccccccccc:",
+            "cccccc;${c2}0MMKxdd:${c1};${c2}MMMkddc.${c1};cccccccccccc;",
+            "ccccc;${c2}XM0'${c1};cccc;${c2}MMM.${c1};cccccccccccccccc'",
+            "ccccc;${c2}MMo${c1};ccccc;${c2}MMW.${c1};ccccccccccccccc; ",
+            "ccccc;${c2}0MNc.${c1}ccc${c2}.xMMd${c1};ccccccccccccccc;  ",
+            "cccccc;${c2}dNMWXXXWM0:${c1};cccccccccccccc:,   ",
+            "cccccccc;${c2}.:odl:.${c1};cccccccccccccc:,.    ",
+            ":cccccccccccccccccccccccccccc:'.      ",
+            ".:cccccccccccccccccccccc:;,..         ",
+            "  '::cccccccccccccc::;,.              ",
+        ],
+        small: &[
+            "        ,'''''. ",
+            "       |   ,.  |",
+            "       |  |  '_'",
+            "  ,....|  |..   ",
+            ".'  ,_;|   ..'  ",
+            "|  |   |  |     ",
+            "|  ',_,'  |     ",
+            " '.     ,'      ",
+            "   '''''        ",
+        ],
+        tiny: &[
+            "   ,--.",
+            " ,-|  '",
+            "( _|-  ",
+            " `--'  ",
+        ],
+    },
+    Logo {
+        names:  &["opensuse", "opensuse-tumbleweed", "opensuse-leap", "opensuse-microos", "suse"],
+        colors: &["green", "bright_white"],
+        large: &[
+            "           .;ldkO0000Okdl;.           ",
+            "       .;d00xl:^^:ok00d;.             ",
+            "     .d00l'                'o00d.     ",
+            "   .d0Kd'  Okxol:;,.          :O0d.   ",
+            "  .OK${c2}KKK0kOKKKKKKKKKKOxo:,      ${c1}lKO.  ",
+            " ,0K${c2}KKKKKKKKKKKKKKK0P^${c1},,,${c2}^dx:${c1}    ;00, ",
+            ".OK${c2}KKKKKKKKKKKKKKKk'${c1}.oOPPb.${c2}'0k.${c1}   cKO.",
+            ":KK${c2}KKKKKKKKKKKKKKK: ${c1}kKx..dd ${c2}lKd${c1}   'OK:",
+            "dKK${c2}KKKKKKKKKOx0KKKd ${c1}^0KKKO' ${c2}kKKc${c1}   dKd",
+            "dKK${c2}KKKKKKKKKK;.;oOKx,..${c1}^${c2}..;kKKK0.${c1}  dKd",
+            ":KK${c2}KKKKKKKKKK0o;...^cdxxOK0O/^^'  ${c1}.0K:",
+            " kKK${c2}KKKKKKKKKKKKK0x;,,......,;od  ${c1}lKk ",
+            " '0K${c2}KKKKKKKKKKKKKKKKKKKK00KKOo^  ${c1}c00' ",
+            "  'kK${c2}KKOxddxkOO00000Okxoc;''   ${c1}.dKk'  ",
+            "    l0Ko.                    .c00l'   ",
+            "     'l0Kk:.              .;xK0l'     ",
+            "        'lkK0xl:;,,,,;:ldO0kl'        ",
+            "            '^:ldxkkkkxdl:^'          ",
+        ],
+        small: &[
+            "  _______  ",
+            "__|   __ \\ ",
+            "     / .\\ \\",
+            "     \\__/ |",
+            "   _______|",
+            "   \\_______",
+            "__________/",
+        ],
+        tiny: &[
+            " __ _   ",
+            "|  (o)\\ ",
+            " `-----'",
+        ],
+    },
+    Logo {
+        names:  &["nixos"],
+        colors: &["blue", "cyan"],
+        large: &[
+            "          ▗▄▄▄       ${c2}▗▄▄▄▄    ▄▄▄▖${c1}         ",
+            "          ▜███▙       ${c2}▜███▙  ▟███▛${c1}         ",
+            "           ▜███▙       ${c2}▜███▙▟███▛${c1}          ",
+            "            ▜███▙       ${c2}▜██████▛${c1}           ",
+            "     ▟█████████████████▙ ${c2}▜████▛     ▟▙${c1}     ",
+            "    ▟███████████████████▙ ${c2}▜███▙    ▟██▙${c1}    ",
+            "${c2}           ▄▄▄▄▖           ▜███▙  ${c1}▟███▛    ",
+            "${c2}          ▟███▛             ▜██▛ ${c1}▟███▛     ",
+            "${c2}         ▟███▛               ▜▛ ${c1}▟███▛      ",
+            "${c2}▟███████████▛                  ${c1}▟██████████▙",
+            "${c2}▜██████████▛                  ${c1}▟███████████▛",
+            "${c2}      ▟███▛ ${c1}▟▙               ▟███▛         ",
+            "${c2}     ▟███▛ ${c1}▟██▙             ▟███▛          ",
+            "${c2}    ▟███▛  ${c1}▜███▙           ▝▀▀▀▀           ",
+            "${c2}    ▜██▛    ${c1}▜███▙ ${c2}▜██████████████████▛     ",
+            "${c2}     ▜▛     ${c1}▟████▙ ${c2}▜████████████████▛      ",
+            "           ${c1}▟██████▙       ${c2}▜███▙            ",
+            "          ${c1}▟███▛▜███▙       ${c2}▜███▙           ",
+            "         ${c1}▟███▛  ▜███▙       ${c2}▜███▙          ",
+            "         ${c1}▝▀▀▀    ▀▀▀▀▘       ${c2}▀▀▀▘          ",
+        ],
+        small: &[
+            "  \\\\  \\\\ ${c2}//${c1}  ",
+            " ==\\\\__\\\\${c2}/ //${c1}",
+            "${c2}   //   \\\\// ",
+            "==//     //==",
+            " //\\\\___//   ",
+            "// /\\\\  \\\\== ",
+            "${c1}  // \\\\  \\\\  ",
+        ],
+        tiny: &[
+            " \\\\ ${c2}//${c1}",
+            "=\\\\${c2}//=",
+            " //\\\\ ",
+            "${c1}// \\\\ ",
+        ],
+    },
+    Logo {
+        names:  &["gentoo"],
+        colors: &["magenta", "bright_white"],
+        large: &[
+            "         -/oyddmdhs+:.             ",
+            "     -o${c2}dNMMMMMMMMNNmhy+${c1}-`          ",
+            "   -y${c2}NMMMMMMMMMMMNNNmmdhy${c1}+-        ",
+            " `o${c2}mMMMMMMMMMMMMNmdmmmmddhhy${c1}/`     ",
+            " om${c2}MMMMMMMMMMMN${c1}hhyyyo${c2}hmdddhhhd${c1}o`   ",
+            ".y${c2}dMMMMMMMMMMd${c1}hs++so/s${c2}mdddhhhhdm${c1}+` ",
+            " oy${c2}hdmNMMMMMMMN${c1}dyooy${c2}dmddddhhhhyhN${c1}d.",
+            "  :o${c2}yhhdNNMMMMMMMNNNmmdddhhhhhyym${c1}Mh",
+            "    .:${c2}+sydNMMMMMNNNmmmdddhhhhhhmM${c1}my",
+            "       /m${c2}MMMMMMNNNmmmdddhhhhhmMNh${c1}s:",
+            "    `o${c2}NMMMMMMMNNNmmmddddhhdmMNhs${c1}+` ",
+            "  `s${c2}NMMMMMMMMNNNmmmdddddmNMmhs${c1}/.   ",
+            " /N${c2}MMMMMMMMNNNNmmmdddmNMNdso${c1}:`     ",
+            "+M${c2}MMMMMMNNNNNmmmmdmNMNdso${c1}/-        ",
+            "yM${c2}MNNNNNNNmmmmmNNMmhs+/${c1}-`          ",
+            "/h${c2}MMNNNNNNNNMNdhs++/${c1}-`             ",
+            "`/${c2}ohdmmddhys+++/:${c1}.`                ",
+            "  `-//////:--.                     ",
+        ],
+        small: &[
+            " _-----_   ",
+            "(       \\  ",
+            "\\    0   \\ ",
+            "${c2} \\        )",
+            " /      _/ ",
+            "(     _-   ",
+            "\\____-     ",
+        ],
+        tiny: &[
+            " _---_ ",
+            "(  o  \\",
+            " ${c2}/  _/ ",
+            "(__-   ",
+        ],
+    },
+    Logo {
+        names:  &["void"],
+        colors: &["green", "bright_black"],
+        large: &[
+            "                __.;=====;.__                ",
+            "            _.=+==++=++=+=+===;.             ",
+            "             -=+++=+===+=+=+++++=_           ",
+            "        .     -=:``     `--==+=++==.         ",
+            "       _vi,    `            --+=++++:        ",
+            "      .uvnvi.       _._       -==+==+.       ",
+            "     .vvnvnI`    .;==|==;.     :|=||=|.      ",
+            "${c2}+QmQQm${c1}pvvnv; ${c2}_yYsyQQWUUQQQm #QmQ#${c1}:${c2}QQQWUV$QQm.",
+            "${c2} -QQWQW${c1}pvvo${c2}wZ?.wQQQE${c1}==<${c2}QWWQ/QWQW.QQWW${c1}(: ${c2}jQWQE",
+            "${c2}  -$QQQQmmU'  jQQQ@${c1}+=<${c2}QWQQ)mQQQ.mQQQC${c1}+;${c2}jWQQ@'",
+            "${c2}   -$WQ8Y${c1}nI:   ${c2}QWQQwgQQWV${c1}`${c2}mWQQ.jQWQQgyyWW@!  ",
+            "${c1}     -1vvnvv.     `~+++`        ++|+++       ",
+            "      +vnvnnv,                 `-|===        ",
+            "       +vnvnvns.           .      :=-        ",
+            "        -Invnvvnsi..___..=sv=.     `         ",
+            "          +Invnvnvnnnnnnnnvvnn;.             ",
+            "            ~|Invnvnvvnvvvnnv}+`             ",
+            "               -~\"|{*l}*|\"\"~                 ",
+        ],
+        small: &[
+            "    _______  ",
+            " _ \\______ - ",
+            "| \\  ___  \\ |",
+            "| | /   \\ | |",
+            "| | \\___/ | |",
+            "| \\______ \\_|",
+            " -_______\\   ",
+        ],
+        tiny: &[
+            "  ____  ",
+            " / __ \\ ",
+            "| (__) |",
+            " \\____/ ",
+        ],
+    },
+    Logo {
+        names:  &["alpine"],
+        colors: &["blue"],
+        large: &[
+            "       .hddddddddddddddddddddddh.       ",
+            "      :dddddddddddddddddddddddddd:      ",
+            "     /dddddddddddddddddddddddddddd/     ",
+            "    +dddddddddddddddddddddddddddddd+    ",
+            "  `sdddddddddddddddddddddddddddddddds`  ",
+            " `ydddddddddddd++hdddddddddddddddddddy` ",
+            ".hddddddddddd+`  `+ddddh:-sdddddddddddh.",
+            "hdddddddddd+`      `+y:    .sddddddddddh",
+            "ddddddddh+`   `//`   `.`     -sddddddddd",
+            "ddddddh+`   `/hddh/`   `:s-    -sddddddd",
+            "ddddh+`   `/+/dddddh/`   `+s-    -sddddd",
+            "ddd+`   `/o` :dddddddh/`   `oy-    .yddd",
+            "hdddyo+ohddyosdddddddddho+oydddy++ohdddh",
+            ".hddddddddddddddddddddddddddddddddddddh.",
+            " `yddddddddddddddddddddddddddddddddddy` ",
+            "  `sdddddddddddddddddddddddddddddddds`  ",
+            "    +dddddddddddddddddddddddddddddd+    ",
+            "     /dddddddddddddddddddddddddddd/     ",
+            "      :dddddddddddddddddddddddddd:      ",
+            "       .hddddddddddddddddddddddh.       ",
+        ],
+        small: &[
+            "   /\\ /\\    ",
+            "  // \\  \\   ",
+            " //   \\  \\  ",
+            "///    \\  \\ ",
+            "//      \\  \\",
+            "         \\  ",
+        ],
+        tiny: &[
+            "  /\\ /\\  ",
+            " //\\\\  \\ ",
+            "//  \\\\  \\",
+        ],
+    },
+    Logo {
+        names:  &["kali"],
+        colors: &["blue", "bright_black"],
+        large: &[
+            "..............                                  ",
+            "            ..,;:ccc,.                          ",
+            "          ......''';lxO.                        ",
+            ".....''''..........,:ld;                        ",
+            "           .';;;:::;,,.x,                       ",
+            "      ..'''.            0Xxoc:,.  ...           ",
+            "  ....                ,ONkc;,;cokOdc',.         ",
+            " .                   OMo           ':${c2}dd${c1}o.       ",
+            "                    dMc               :OO;      ",
+            "                    0M.                 .:o.    ",
+            "                    ;Wd                         ",
+            "                     ;XO,                       ",
+            "                       ,d0Odlc;,..              ",
+            "                           ..',;:cdOOd::,.      ",
+            "                                    .:d;.':;.   ",
+            "                                       'd,  .'  ",
+            "                                         ;l   ..",
+            "                                          .o    ",
+            "                                            c   ",
+            "                                            .'  ",
+            "                                             .  ",
+        ],
+        small: &[
+            "  __          ",
+            " /  \\__       ",
+            " \\_    \\___   ",
+            "   \\       \\  ",
+            "    '--._   \\ ",
+            "         '.  |",
+            "           \\ |",
+            "            \\|",
+        ],
+        tiny: &[
+            " _     ",
+            "/ \\__  ",
+            "\\_   \\_",
+            "  '-. \\",
+            "     \\|",
+        ],
+    },
+    Logo {
+        names:  &["rocky"],
+        colors: &["green"],
+        large: &[
+            "          __wgliliiligw_,          ",
+            "       _williiiiiiliilililw,       ",
+            "     _%iiiiiilililiiiiiiiiiii_     ",
+            "   .Qliiiililiiiiiiililililiilm.   ",
+            "  _iiiiiliiiiiililiiiiiiiiiiliil,  ",
+            " .lililiiilililiiiilililililiiiii, ",
+            "_liiiiiiliiiiiiiliiiiiF{iiiiiilili,",
+            "jliililiiilililiiili@`  ~ililiiiiiL",
+            "iiiliiiiliiiiiiili>`      ~liililii",
+            "liliiiliiilililii`         -9liiiil",
+            "iiiiiliiliiiiii~             \"4lili",
+            "4ililiiiiilil~|      -w,       )4lf",
+            "-liiiiililiiw___,,,,,__ \"t___,,_ii'",
+            " \\iiiiiiiiiilililililiiiiiiiilili~ ",
+            "  ~tiiiiiiiilililiiiiiiilililillF  ",
+            "    `VlililiiiiiiiiiilililiilF^    ",
+            "      ~\"4lililiiiiiiiiiiliiF~      ",
+            "           ~~~\"*****\"~~~           ",
+        ],
+        small: &[
+            "   .-/+++++/-.  ",
+            " .+++++++++++++.",
+            "/++++++++/-/++++",
+            "+++++++/.   ./++",
+            "++++/.  .:.   .:",
+            " /:.  .:+++:.   ",
+            "  .  -+++++++-  ",
+            "      `-/+/-`   ",
+        ],
+        tiny: &[
+            " .-+++-. ",
+            "/+++/ \\+\\",
+            "\\+/ .-. /",
+            "  `-+++' ",
+        ],
+    },
+    Logo {
+        names:  &["almalinux", "alma"],
+        colors: &["yellow", "bright_red", "blue", "green"],
+        large: &[
+            "         'c:.                           ",
+            "        lkkkx, ..       ${c2}..   ,cc,       ",
+            "        ${c1}okkkk:ckkx'  ${c2}.lxkkx.okkkkd      ",
+            "        ${c1}.:llcokkx'  ${c2}:kkkxkko:xkkd,      ",
+            "      ${c1}.xkkkkdood:  ${c2};kx,  .lkxlll;       ",
+            "       ${c1}xkkx.       ${c2}xk'     xkkkkk:      ",
+            "       ${c1}'xkx.       ${c2}xd      .....,.      ",
+            "      ${c3}.. ${c1}:xkl'     ${c2}:c      ..''..       ",
+            "    ${c3}.dkx'  ${c1}.:ldl:'. ${c2}'  ${c4}':lollldkkxo;    ",
+            "  ${c3}.''lkkko'                     ${c4}ckkkx.  ",
+            "${c3}'xkkkd:kkd.       ..  ${c4};'        :kkxo.  ",
+            "${c3},xkkkd;kk'      ,d;    ${c4}ld.   ':dkd::cc, ",
+            " ${c3}.,,.;xkko'.';lxo.      ${c4}dx,  :kkk'xkkkkc",
+            "     ${c3}'dkkkkkxo:.        ${c4};kx  .kkk:;xkkd.",
+            "       ${c3}.....   ${c4}.;dk:.   lkk.  :;,       ",
+            "             :kkkkkkkdoxkkx             ",
+            "              ,c,,;;;:xkkd.             ",
+            "                ;kkkkl...               ",
+            "                ;kkkkl                  ",
+            "                 ,od;                   ",
+        ],
+        small: &[
+            "   'c:.    ${c2}.lkx.   ",
+            "   ${c1}okkkx  ${c2}:kkxk,   ",
+            "${c3}.dkx.        ${c4}:dkxo.",
+            "${c3}'xkkd.      ${c4}'kkxx' ",
+            "     ${c4}:kkkkdoxk     ",
+            "      ;kkkl.       ",
+        ],
+        tiny: &[
+            " 'c.  ${c2}.c, ",
+            "${c3}.dx.   ${c4}.xo",
+            "   ${c4}:kxk'  ",
+        ],
+    },
+    Logo {
+        names:  &["raspbian"],
+        colors: &["green", "red"],
+        large: &[
+            "   `.::///+:/-.        --///+//-:``",
+            " `+oooooooooooo:   `+oooooooooooo: ",
+            "  /oooo++//ooooo:  ooooo+//+ooooo. ",
+            "  `+ooooooo:-:oo-  +o+::/ooooooo:  ",
+            "   `:oooooooo+``    `.oooooooo+-   ",
+            "     `:++ooo/.        :+ooo+/.`    ",
+            "${c2}        ...`  `.----.` ``..        ",
+            "     .::::-``:::::::::.`-:::-`     ",
+            "    -:::-`   .:::::::-`  `-:::-    ",
+            "   `::.  `.--.`  `` `.---.``.::`   ",
+            "       .::::::::`  -::::::::` `    ",
+            " .::` .:::::::::- `::::::::::``::. ",
+            "-:::` ::::::::::.  ::::::::::.`:::-",
+            "::::  -::::::::.   `-::::::::  ::::",
+            "-::-   .-:::-.``....``.-::-.   -::-",
+            " .. ``       .::::::::.     `..`.. ",
+            "   -:::-`   -::::::::::`  .:::::`  ",
+            "   :::::::` -::::::::::` :::::::.  ",
+            "   .:::::::  -::::::::. ::::::::   ",
+            "    `-:::::`   ..--.`   ::::::.    ",
+            "      `...`  `...--..`  `...`      ",
+            "            .::::::::::            ",
+            "             `.-::::-`             ",
+        ],
+        small: &[
+            "   .~~.   .~~.   ",
+            "  '. \\ ' ' / .'  ",
+            "${c2}   .~ .~~~..~.   ",
+            "  : .~.'~'.~. :  ",
+            " ~ (   ) (   ) ~ ",
+            "( : '~'.~.'~' : )",
+            " ~ .~       ~. ~ ",
+            "  (  : '~' :  )  ",
+            "   '~ .~~~. ~'   ",
+            "       '~'       ",
+        ],
+        tiny: &[
+            " .~. .~. ",
+            "${c2}(.~(_)~.)",
+            " ( (_) ) ",
+            "  '~~~'  ",
+        ],
+    },
+    Logo {
+        names:  &["android", "termux"],
+        colors: &["green", "bright_white"],
+        large: &[
+            "         -o          o-         ",
+            "          +hydNNNNdyh+          ",
+            "        +mMMMMMMMMMMMMm+        ",
+            "      `dMM${c2}m:${c1}NMMMMMMN${c2}:m${c1}MMd`      ",
+            "      hMMMMMMMMMMMMMMMMMMh      ",
+            "  ..  yyyyyyyyyyyyyyyyyyyy  ..  ",
+            ".mMMm`MMMMMMMMMMMMMMMMMMMM`mMMm.",
+            ":MMMM-MMMMMMMMMMMMMMMMMMMM-MMMM:",
+            ":MMMM-MMMMMMMMMMMMMMMMMMMM-MMMM:",
+            ":MMMM-MMMMMMMMMMMMMMMMMMMM-MMMM:",
+            ":MMMM-MMMMMMMMMMMMMMMMMMMM-MMMM:",
+            "-MMMM-MMMMMMMMMMMMMMMMMMMM-MMMM-",
+            " +yy+ MMMMMMMMMMMMMMMMMMMM +yy+ ",
+            "      mMMMMMMMMMMMMMMMMMMm      ",
+            "      `/++MMMMh++hMMMM++/`      ",
+            "          MMMMo  oMMMM          ",
+            "          MMMMo  oMMMM          ",
+            "          oNMm-  -mMNs          ",
+        ],
+        small: &[
+            "  ;,           ,;  ",
+            "   ';,.-----.,;'   ",
+            "  ,'           ',  ",
+            " /    ${c2}O     O${c1}    \\ ",
+            "|                 |",
+            "'-----------------'",
+        ],
+        tiny: &[
+            " \\.--./ ",
+            " / ${c2}o o${c1} \\",
+            "'------'",
+        ],
+    },
+    Logo {
+        names:  &["apple", "macos", "macbook", "yablocoder"],
+        colors: &["green", "yellow", "red", "bright_red", "magenta", "blue"],
+        large: &[
+            "                              ",
+            "${c1}                    c.'       ",
+            "                 ,xNMM.       ",
+            "               .OMMMMo        ",
+            "               lMM\"           ",
+            "     .;loddo:.  .olloddol;.   ",
+            "${c2}   cKMMMMMMMMMMNWMMMMMMMMMM0: ",
+            " .KMMMMMMMMMMMMMMMMMMMMMMMWd. ",
+            " XMMMMMMMMMMMMMMMMMMMMMMMX.   ",
+            "${c3};MMMMMMMMMMMMMMMMMMMMMMMM:    ",
+            ":MMMMMMMMMMMMMMMMMMMMMMMM:    ",
+            "${c4}.MMMMMMMMMMMMMMMMMMMMMMMMX.   ",
+            " kMMMMMMMMMMMMMMMMMMMMMMMMWd. ",
+            " 'XMMMMMMMMMMMMMMMMMMMMMMMMMMk",
+            "${c5}  'XMMMMMMMMMMMMMMMMMMMMMMMMK.",
+            "    kMMMMMMMMMMMMMMMMMMMMMMd  ",
+            "${c6}     ;KMMMMMMMWXXWMMMMMMMk.   ",
+            "       \"cooc*\"    \"*coo'\"     ",
+            "                              ",
+        ],
+        small: &[
+            "${c1}        .:'   ",
+            "    __ :'__   ",
+            "${c2} .'`  `-'  ``.",
+            "${c3}:          .-'",
+            "${c4}:         :   ",
+            "${c5} :         `-;",
+            "${c6}  `.__.-.__.' ",
+        ],
+        tiny: &[
+            "${c1}    .:  ",
+            "${c2} .'`-'`.",
+            "${c3}:      :",
+            "${c5} `.__.' ",
+        ],
+    },
+    Logo {
+        names:  &["linux"],
+        colors: &["bright_black", "bright_white", "yellow"],
+        large: &[
+            "        #####        ",
+            "       #######       ",
+            "       ##${c2}O${c1}#${c2}O${c1}##       ",
+            "       #${c3}#####${c1}#       ",
+            "     ##${c2}##${c3}###${c2}##${c1}##     ",
+            "    #${c2}##########${c1}##    ",
+            "   #${c2}############${c1}##   ",
+            "   #${c2}############${c1}###  ",
+            "  ${c3}##${c1}#${c2}###########${c1}##${c3}#  ",
+            "${c3}######${c1}#${c2}#######${c1}#${c3}######",
+            "${c3}#######${c1}#${c2}#####${c1}#${c3}#######",
+            "${c3}  #####${c1}#######${c3}#####  ",
+        ],
+        small: &[
+            "    ___   ",
+            "   (${c2}.. ${c1}|  ",
+            "   (${c3}<> ${c1}|  ",
+            "  / ${c2}__  ${c1}\\ ",
+            " ( ${c2}/  \\ ${c1}/|",
+            "${c3}_${c1}/\\ ${c2}__)${c1}/${c3}_${c1})",
+            "${c3}\\/${c1}-____${c3}\\/ ",
+        ],
+        tiny: &[
+            "  .--.  ",
+            " |${c2}o${c3}_${c2}o${c1} | ",
+            " |${c3}:_/${c1} | ",
+            "//   \\ \\",
+        ],
+    },
+    Logo {
+        names:  &["freebsd"],
+        colors: &["red", "bright_white"],
+        large: &[
+            "${c2}```                        ${c1}`    ",
+            "${c2}  ` `.....---...${c1}....--.```   -/ ",
+            "${c2}  +o   .--`         ${c1}/y:`      +.",
+            "${c2}   yo`:.            ${c1}:o      `+- ",
+            "${c2}    y/               ${c1}-/`   -o/  ",
+            "${c2}   .-                  ${c1}::/sy+:. ",
+            "${c2}   /                     ${c1}`--  / ",
+            "${c2}  `:                          ${c1}:`",
+            "${c2}  `:                          ${c1}:`",
+            "${c2}   /                          ${c1}/ ",
+            "${c2}   .-                        ${c1}-. ",
+            "${c2}    --                      ${c1}-.  ",
+            "${c2}     `:`                  ${c1}`:`   ",
+            "${c2}       .--             `--.     ",
+            "${c2}          .---.....----.        ",
+        ],
+        small: &[
+            "/\\,-'''''-,/\\",
+            "\\_)       (_/",
+            "|           |",
+            "|           |",
+            " ;         ; ",
+            "  '-_____-'  ",
+        ],
+        tiny: &[
+            "/\\,-'''-,/\\",
+            "\\_)     (_/",
+            " ;       ; ",
+            "  '-___-'  ",
+        ],
+    },
+    Logo {
+        names:  &["omarchy"],
+        colors: &["bright_green"],
+        large: &[
+            "██████████████████████",
+            "██████████████████████",
+            "████              ████",
+            "████              ████",
+            "████              ████",
+            "████              ████",
+            "████              ████",
+            "████              ████",
+            "████          ████████",
+            "████          ████████",
+            "██████████████████    ",
+            "██████████████████    ",
+        ],
+        small: &[
+            "████████████",
+            "██        ██",
+            "██        ██",
+            "██      ████",
+            "██████████  ",
+        ],
+        tiny: &[
+            "████████",
+            "██    ██",
+            "██  ████",
+            "██████  ",
+        ],
+    },
+];
+
+impl Logo {
+    pub fn variant(&self, size: LogoSize) -> &'static [&'static str] {
+        match size {
+            LogoSize::Tiny  if !self.tiny.is_empty()  => self.tiny,
+            LogoSize::Tiny | LogoSize::Small if !self.small.is_empty() => self.small,
+            _ => self.large,
+        }
+    }
+}
+
+// когда дистрибутив вообще не опознан
+pub const FALLBACK: Logo = Logo {
+    names:  &[],
+    colors: &["bright_cyan", "bright_black"],
+    large: &[
+        "                           ",
+        "                           ",
+        "                           ",
+        " ⠀⠀⠀⠰⡿⠿⠛⠛⠻⠿⣷               ",
+        "⠀⠀⠀⠀⠀⠀⣀⣄⡀⠀⠀⠀⠀⢀⣀⣀⣤⣄⣀⡀       ",
+        "⠀⠀⠀⠀⠀⢸⣿⣿⣷⠀⠀⠀⠀⠛⠛⣿⣿⣿⡛⠿⠷      ",
+        "⠀⠀⠀⠀⠀⠘⠿⠿⠋⠀⠀⠀⠀⠀⠀⣿⣿⣿⠇        ",
+        "⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠈⠉⠁         ",
+        "⠀⠀⠀⠀⣿⣷⣄⠀⢶⣶⣷⣶⣶⣤⣀            ",
+        "⠀⠀⠀⠀⣿⣿⣿⠀⠀⠀⠀⠀⠈⠙⠻⠗           ",
+        "⠀⠀⠀⣰⣿⣿⣿⠀⠀⠀⠀⢀⣀⣠⣤⣴⣶⡄         ",
+        "⠀⣠⣾⣿⣿⣿⣥⣶⣶⣿⣿⣿⣿⣿⠿⠿⠛⠃         ",
+        "⢰⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⡄              ",
+        "⢸⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⡁              ",
+        "⠈⢿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⠁              ",
+        "⠀⠀⠛⢿⣿⣿⣿⣿⣿⣿⡿⠟               ",
+        "⠀⠀⠀⠀⠀⠉⠉⠉                   ",
+        "                           ",
+        "                           ",
+        "${c2}   why do I need Linux?    ",
+    ],
+    small: &[],
+    tiny:  &[],
+};
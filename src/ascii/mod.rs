@@ -1,45 +1,152 @@
 pub mod builtin;
+pub mod custom;
+pub mod logos;
 
-use crate::config::AsciiConfig;
-use crate::utils::unicode_str_width;
-use std::fs;
-
-pub fn load_ascii_art(cfg: &AsciiConfig) -> Vec<String> {
-    if !cfg.enabled { return vec![]; }
-
-    if let Some(path) = &cfg.file {
-        if let Ok(content) = fs::read_to_string(path) {
-            let lines: Vec<&str> = content.lines().collect();
-            let max_width = lines.iter()
-                .map(|l| unicode_str_width(l))
-                .max()
-                .unwrap_or(0);
-
-            let effective_width = cfg.width.max(max_width);
-            
-            return lines.iter().map(|l| {
-                let s = l.to_string();
-    
-                if unicode_str_width(&s) < effective_width {
-                    let padding = effective_width - unicode_str_width(&s);
-                    format!("{}{}", s, " ".repeat(padding))
-                } else {
-                    let mut result = String::new();
-                    let mut current_width = 0;
-
-                    for c in s.chars() {
-                        let char_width = if c as u32 <= 0x7F { 1 } else { 2 };
-                        if current_width + char_width > effective_width {
-                            break;
-                        }
-                        result.push(c);
-                        current_width += char_width;
-                    }
-
-                    result
-                }
-            }).collect();
-        }
-    }
-    builtin::builtin_ascii(cfg.width, cfg.distro.as_deref())
+use crate::config::{AsciiConfig, LogoPosition, Padding};
+use custom::ArtFile;
+use crate::utils::{unicode_str_width, pad_to_width};
+use crate::utils::unicode::{color_marker, last_color_marker};
+
+// палитра для своих артов, если в конфиге ничего не задано
+const DEFAULT_COLORS: &[&str] = &["bright_cyan"];
+
+// сколько места есть под арт, нужно для ascii.size = "auto"
+#[derive(Default)]
+pub struct Fit {
+    pub max_width:  Option<usize>,
+    pub max_height: Option<usize>,
+}
+
+impl Fit {
+    pub fn fits(&self, (width, height): (usize, usize)) -> bool {
+        self.max_width.is_none_or(|w| width <= w)
+            && self.max_height.is_none_or(|h| height <= h)
+    }
+}
+
+pub struct AsciiArt {
+    pub lines:  Vec<String>,
+    // цвета для ${c1}..${c9}, строка без маркеров красится в первый
+    pub colors: Vec<String>,
+    // пожелание самого арта, конфиг важнее
+    pub position: Option<LogoPosition>,
+    // строки уже с цветами (картинка в символах), палитра и градиент не нужны
+    pub painted: bool,
+}
+
+impl AsciiArt {
+    pub fn new(lines: Vec<String>, colors: Vec<String>) -> Self {
+        Self { lines: carry_colors(lines), colors, position: None, painted: false }
+    }
+
+    pub fn painted(lines: Vec<String>) -> Self {
+        Self { lines, colors: vec![], position: None, painted: true }
+    }
+
+    pub fn empty() -> Self {
+        Self { lines: vec![], colors: vec![], position: None, painted: false }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn width(&self) -> usize {
+        self.lines.iter()
+            .map(|l| unicode_str_width(l))
+            .max().unwrap_or(0)
+    }
+
+    // если маркеров больше чем цветов - берём последний цвет
+    pub fn color(&self, idx: usize) -> &str {
+        self.colors.get(idx)
+            .or(self.colors.last())
+            .map(|s| s.as_str())
+            .unwrap_or("white")
+    }
+}
+
+// цвет тянется со строки на строку, поэтому каждой строке дописываем
+// активный маркер в начало - дальше строки можно красить по отдельности
+fn carry_colors(lines: Vec<String>) -> Vec<String> {
+    let mut color = 0;
+
+    lines.into_iter().map(|line| {
+        let carried = match color_marker(&line) {
+            Some(_) => line.clone(),
+            None    => format!("${{c{}}}{}", color + 1, line),
+        };
+        if let Some(last) = last_color_marker(&line) { color = last; }
+
+        carried
+    }).collect()
+}
+
+// ascii.colors важнее ascii.color, а без них - родные цвета логотипа
+pub fn resolve_palette(cfg: &AsciiConfig, defaults: &[&str]) -> Vec<String> {
+    if !cfg.colors.is_empty() {
+        cfg.colors.clone()
+    } else if let Some(color) = &cfg.color {
+        vec![color.clone()]
+    } else {
+        defaults.iter().map(|c| c.to_string()).collect()
+    }
+}
+
+fn apply_padding(lines: Vec<String>, padding: Padding) -> Vec<String> {
+    let (top, right, bottom, left) = padding.sides();
+    let width = lines.iter().map(|l| unicode_str_width(l)).max().unwrap_or(0);
+    let blank = " ".repeat(width + left + right);
+
+    std::iter::repeat_n(blank.clone(), top)
+        .chain(lines.into_iter().map(|l| format!("{}{}{}", " ".repeat(left), l, " ".repeat(right))))
+        .chain(std::iter::repeat_n(blank, bottom))
+        .collect()
+}
+
+fn custom_art(file: ArtFile, cfg: &AsciiConfig) -> AsciiArt {
+    let max_width = file.lines.iter()
+        .map(|l| unicode_str_width(l))
+        .max()
+        .unwrap_or(0);
+
+    let effective_width = cfg.width.max(max_width);
+    let mut lines: Vec<String> = file.lines.iter()
+        .map(|l| pad_to_width(l, effective_width))
+        .collect();
+    if let Some(padding) = file.meta.padding {
+        lines = apply_padding(lines, padding);
+    }
+
+    // цвета из заголовка арта - как родные цвета логотипа
+    let defaults: Vec<&str> = match (&file.meta.colors, &file.meta.color) {
+        (Some(colors), _) => colors.iter().map(|s| s.as_str()).collect(),
+        (None, Some(color)) => vec![color.as_str()],
+        (None, None) => DEFAULT_COLORS.to_vec(),
+    };
+
+    let mut art = AsciiArt::new(lines, resolve_palette(cfg, &defaults));
+    art.position = file.meta.position;
+    art
+}
+
+pub fn load_ascii_art(cfg: &AsciiConfig, fit: &Fit) -> AsciiArt {
+    if !cfg.enabled { return AsciiArt::empty(); }
+
+    let custom = match (&cfg.file, &cfg.name) {
+        (Some(path), _) => Some(custom::read_art_file(path)),
+        (None, Some(name)) => Some(custom::find_named_art(name)
+            .and_then(|path| custom::read_art_path(&path))),
+        (None, None) => None,
+    };
+
+    match custom {
+        Some(Ok(file)) => return custom_art(file, cfg),
+        // не молчим, но и без лого не оставляем
+        Some(Err(e))   => eprintln!("rushfetch: {}, using the built-in logo", e),
+        None           => {}
+    }
+
+    let (lines, defaults) = builtin::builtin_ascii(cfg.width, cfg.distro.as_deref(), cfg.size, fit);
+    AsciiArt::new(lines, resolve_palette(cfg, defaults))
 }
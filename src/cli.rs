@@ -0,0 +1,139 @@
+use std::env;
+use std::process::exit;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static DEBUG: AtomicBool = AtomicBool::new(false);
+
+const HELP: &str = "\
+rushfetch - system information, fast
+
+usage: rushfetch [options]
+       rushfetch migrate-config [FILE]
+
+options:
+  -c, --config FILE      extra config file on top of /etc and ~/.config ones
+  -s, --set KEY=VALUE    override one config key: --set layout.style=box
+  -d, --debug            print what custom commands did (stderr, exit codes, timings)
+      --explain-config   show which config files and [[when]] blocks apply here
+      --check-config     report config problems with file:line:column, exit 1 if any
+      --print-config     print the effective config, all layers and [[when]] blocks merged
+      --gen-config [FILE]
+                         write a documented config with the defaults (FILE, - for stdout,
+                         or ~/.config/rushfetch/config.toml)
+      --force            let --gen-config overwrite an existing file
+      --detect           let --gen-config pick this distro's logo and drop fields
+                         this machine doesn't have (gpu, swap, de)
+  -h, --help             show this help
+  -V, --version          show version
+
+commands:
+  migrate-config [FILE]  rewrite an older config (FILE, --config or the ~/.config one)
+                         to the current schema, keeping comments; the old file is kept as .bak
+
+RUSHFETCH_DEBUG=1 works like --debug, RUSHFETCH_CONFIG=FILE like --config.
+Any config key can be set from the environment too, `__` separates sections:
+  RUSHFETCH_LAYOUT__STYLE=box RUSHFETCH_LANGUAGE=ru rushfetch
+RUSHFETCH_STRICT=true (or strict = true in the config) turns config warnings into errors.
+";
+
+pub struct Args {
+    pub config:         Option<String>,
+    // "key.path=value" из --set, по порядку
+    pub set:            Vec<String>,
+    pub debug:          bool,
+    pub explain_config: bool,
+    pub check_config:   bool,
+    pub print_config:   bool,
+    pub gen_config:     bool,
+    // куда писать --gen-config, None - в ~/.config
+    pub gen_file:       Option<String>,
+    pub force:          bool,
+    pub detect:         bool,
+    pub migrate_config: bool,
+    // файл для migrate-config, если указан
+    pub migrate_file:   Option<String>,
+    // служебный: так кэш обновляется в фоне, в help его нет
+    pub refresh_cache:  bool,
+}
+
+impl Args {
+    // руками, ради пары флагов clap не нужен
+    pub fn parse() -> Self {
+        let mut args = Args {
+            config:         env::var("RUSHFETCH_CONFIG").ok().filter(|c| !c.is_empty()),
+            set:            Vec::new(),
+            debug:          env::var("RUSHFETCH_DEBUG").is_ok_and(|v| !v.is_empty() && v != "0"),
+            explain_config: false,
+            check_config:   false,
+            print_config:   false,
+            gen_config:     false,
+            gen_file:       None,
+            force:          false,
+            detect:         false,
+            migrate_config: false,
+            migrate_file:   None,
+            refresh_cache:  false,
+        };
+
+        let mut rest = env::args().skip(1).peekable();
+        while let Some(arg) = rest.next() {
+            // --config FILE и --config=FILE
+            let (arg, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+                _ => (arg, None),
+            };
+            let mut value = || inline.clone().or_else(|| rest.next()).unwrap_or_else(|| {
+                eprintln!("rushfetch: {} needs a value, see --help", arg);
+                exit(2);
+            });
+
+            match arg.as_str() {
+                "-c" | "--config"  => args.config = Some(value()),
+                "-s" | "--set"     => args.set.push(value()),
+                "-d" | "--debug"   => args.debug = true,
+                "--explain-config" => args.explain_config = true,
+                "--check-config"   => args.check_config = true,
+                "--print-config"   => args.print_config = true,
+                // файл необязателен: --gen-config, --gen-config FILE, --gen-config=FILE,
+                // а можно и после других флагов: --gen-config --detect -
+                "--gen-config"     => {
+                    args.gen_config = true;
+                    args.gen_file = inline.clone().or_else(|| rest.next_if(|next| next == "-" || !next.starts_with('-')));
+                }
+                "--force"          => args.force = true,
+                "--detect"         => args.detect = true,
+                "--refresh-cache"  => args.refresh_cache = true,
+                "migrate-config"   => args.migrate_config = true,
+                file if args.migrate_config && args.migrate_file.is_none() && !file.starts_with('-') =>
+                    args.migrate_file = Some(file.to_string()),
+                file if args.gen_config && args.gen_file.is_none() && (file == "-" || !file.starts_with('-')) =>
+                    args.gen_file = Some(file.to_string()),
+                "-h" | "--help"    => {
+                    print!("{}", HELP);
+                    exit(0);
+                }
+                "-V" | "--version" => {
+                    println!("rushfetch {}", env!("CARGO_PKG_VERSION"));
+                    exit(0);
+                }
+                other => {
+                    eprintln!("rushfetch: unknown option {}, see --help", other);
+                    exit(2);
+                }
+            }
+        }
+
+        DEBUG.store(args.debug, Ordering::Relaxed);
+        args
+    }
+}
+
+pub fn debug_enabled() -> bool {
+    DEBUG.load(Ordering::Relaxed)
+}
+
+pub fn debug(msg: &str) {
+    if debug_enabled() {
+        eprintln!("rushfetch: [debug] {}", msg);
+    }
+}
@@ -0,0 +1,529 @@
+use crate::ascii::custom::find_named_art;
+use crate::config::layers::{merge, merge_layers, Layer};
+use crate::config::when::CONDITIONS;
+use crate::config::*;
+use crate::utils::expand_home;
+use serde::de::{self, Deserializer, Visitor};
+use std::fmt;
+use toml::{Table, Value};
+use toml_edit::ImDocument;
+
+// что не так и где: путь ключа в слитом конфиге, "custom_fields.2.regex"
+pub struct Problem {
+    pub path:    String,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { path: path.into(), message: message.into() }
+    }
+}
+
+// то же, но уже с файлом и строкой: "/etc/rushfetch/config.toml:12:5"
+pub struct Diagnostic {
+    pub location: String,
+    pub message:  String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.location.as_str() {
+            ""       => write!(f, "{}", self.message),
+            location => write!(f, "{}: {}", location, self.message),
+        }
+    }
+}
+
+// списки полей и вариантов берём у самого serde: derive отдаёт их в deserialize_struct/enum
+struct Probe<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for Probe<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("probe"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _: &'static str, fields: &'static [&'static str], _: V) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("probe"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _: &'static str, variants: &'static [&'static str], _: V) -> Result<V::Value, Self::Error> {
+        *self.0 = variants;
+        Err(de::Error::custom("probe"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
+
+pub fn names_of<'de, T: serde::Deserialize<'de>>() -> &'static [&'static str] {
+    let mut names: &'static [&'static str] = &[];
+    let _ = T::deserialize(Probe(&mut names));
+    names
+}
+
+// какие ключи бывают в таблице по этому пути; индексы массивов - "*"
+fn known_keys(path: &[&str]) -> Vec<&'static str> {
+    let mut keys = match path {
+        []                                   => names_of::<Config>().to_vec(),
+        ["theme"]                            => names_of::<Theme>().to_vec(),
+        ["icons"]                            => names_of::<IconSet>().to_vec(),
+        ["ascii"]                            => names_of::<AsciiConfig>().to_vec(),
+        ["ascii", "gradient"]                => names_of::<GradientConfig>().to_vec(),
+        ["logo"]                             => names_of::<LogoConfig>().to_vec(),
+        ["layout"]                           => names_of::<LayoutConfig>().to_vec(),
+        ["labels"]                           => names_of::<LabelConfig>().to_vec(),
+        ["palette"]                          => names_of::<PaletteConfig>().to_vec(),
+        ["commands"]                         => names_of::<CommandsConfig>().to_vec(),
+        ["cache"]                            => names_of::<CacheConfig>().to_vec(),
+        ["format"] | ["cache", "ttl"]        => names_of::<InfoField>().to_vec(),
+        ["custom_fields", "*"]               => names_of::<CustomField>().to_vec(),
+        ["custom_fields", "*", "colors", "*"] => names_of::<ColorRule>().to_vec(),
+        ["categories", "*"]                  => names_of::<CategoryConfig>().to_vec(),
+        _                                    => vec![],
+    };
+    // эти разбираются до десериализации
+    if path.is_empty() { keys.extend(["include", "when"]); }
+    keys
+}
+
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = (prev + usize::from(ca != cb)).min(row[j] + 1).min(cur + 1);
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+// ближайшее имя, если опечатка не слишком большая
+pub fn suggest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (word.chars().count() / 3).max(1) + 1;
+    candidates.into_iter()
+        .map(|c| (distance(&word.to_lowercase(), &c.to_lowercase()), c))
+        .filter(|&(d, _)| d <= limit)
+        .min_by_key(|&(d, _)| d)
+        .map(|(_, c)| c)
+}
+
+pub fn did_you_mean(suggestion: Option<&str>) -> String {
+    suggestion.map(|s| format!(", did you mean `{}`?", s)).unwrap_or_default()
+}
+
+fn ignored_path(path: &serde_ignored::Path, out: &mut Vec<String>) {
+    use serde_ignored::Path;
+    match path {
+        Path::Root => {}
+        Path::Seq { parent, index } => { ignored_path(parent, out); out.push(index.to_string()); }
+        Path::Map { parent, key }   => { ignored_path(parent, out); out.push(key.clone()); }
+        Path::Some { parent } | Path::NewtypeStruct { parent } | Path::NewtypeVariant { parent } => ignored_path(parent, out),
+    }
+}
+
+fn segments(path: &serde_path_to_error::Path) -> Vec<String> {
+    use serde_path_to_error::Segment;
+    path.iter().filter_map(|segment| match segment {
+        Segment::Seq { index } => Some(index.to_string()),
+        Segment::Map { key }   => Some(key.clone()),
+        _ => None,
+    }).collect()
+}
+
+// "categories.2.fields" -> ["categories", "*", "fields"]
+fn schema_path(path: &[String]) -> Vec<&str> {
+    path.iter().map(|s| if s.parse::<usize>().is_ok() { "*" } else { s.as_str() }).collect()
+}
+
+// строка и колонка ключа в тексте файла, или ближайшего найденного родителя
+fn locate(source: &str, path: &[String]) -> Option<(usize, usize)> {
+    let doc = ImDocument::parse(source).ok()?;
+    let mut item = doc.as_item();
+    let mut span = None;
+
+    for segment in path {
+        let next = match item.as_table_like() {
+            Some(table) => table.get_key_value(segment).map(|(key, next)| {
+                span = key.span().or(next.span()).or(span.clone());
+                next
+            }),
+            None => segment.parse::<usize>().ok().and_then(|i| item.get(i)).inspect(|next| {
+                span = next.span().or(span.clone());
+            }),
+        };
+        match next {
+            Some(next) => item = next,
+            None       => break,
+        }
+    }
+
+    let offset = span?.start;
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let col = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    Some((line, col))
+}
+
+fn split(path: &str) -> Vec<String> {
+    path.split('.').filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+fn where_in(layer: &Layer, path: &[String]) -> String {
+    match layer.source.as_deref().and_then(|source| locate(source, path)) {
+        Some((line, col)) => format!("{}:{}:{}", layer.origin, line, col),
+        None              => layer.origin.clone(),
+    }
+}
+
+fn contains(table: &Table, path: &[String]) -> bool {
+    let mut value = match path.first().and_then(|key| table.get(key)) {
+        Some(value) => value,
+        None        => return false,
+    };
+    for segment in &path[1..] {
+        let next = match value {
+            Value::Table(t) => t.get(segment),
+            Value::Array(a) => segment.parse::<usize>().ok().and_then(|i| a.get(i)),
+            _ => None,
+        };
+        match next {
+            Some(next) => value = next,
+            None       => return false,
+        }
+    }
+    true
+}
+
+// ключ в слитом конфиге берётся из последнего слоя, где он есть
+fn locate_in_layers(layers: &[Layer], path: &str) -> String {
+    let path = split(path);
+    (1..=path.len()).rev()
+        .find_map(|len| layers.iter().rev().find(|l| contains(&l.table, &path[..len])).map(|l| where_in(l, &path)))
+        .unwrap_or_default()
+}
+
+// путь в урезанной таблице -> путь в исходной: выкинутые элементы массивов сдвигают индексы
+fn original_path(path: &[String], removed: &[(Vec<String>, usize)]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for segment in path {
+        let mapped = match segment.parse::<usize>() {
+            Ok(mut index) => {
+                let mut gone: Vec<usize> = removed.iter().filter(|(p, _)| *p == out).map(|&(_, i)| i).collect();
+                gone.sort();
+                for i in gone {
+                    if i <= index { index += 1; }
+                }
+                index.to_string()
+            }
+            Err(_) => segment.clone(),
+        };
+        out.push(mapped);
+    }
+    out
+}
+
+fn remove_path(table: &mut Table, path: &[String]) -> bool {
+    let Some((last, parents)) = path.split_last() else { return false };
+    let Some((first, rest)) = parents.split_first() else { return table.remove(last).is_some() };
+
+    let mut value = match table.get_mut(first) {
+        Some(value) => value,
+        None        => return false,
+    };
+    for segment in rest {
+        let next = match value {
+            Value::Table(t) => t.get_mut(segment),
+            Value::Array(a) => segment.parse::<usize>().ok().and_then(|i| a.get_mut(i)),
+            _ => None,
+        };
+        match next {
+            Some(next) => value = next,
+            None       => return false,
+        }
+    }
+    match value {
+        Value::Table(t) => t.remove(last).is_some(),
+        Value::Array(a) => match last.parse::<usize>() {
+            Ok(i) if i < a.len() => { a.remove(i); true }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// один файл (или блок [[when]]) отдельно, поверх встроенных значений - так
+// неполная [theme] не ошибка, а ошибка указывает на свой файл.
+// serde встаёт на первой ошибке: выкидываем сломанный ключ и смотрим дальше.
+// лишние ключи идут в unknown, выкинутые - в dropped
+fn check_table(layer: &Layer, table: &mut Table, prefix: &[String], unknown: &mut Vec<Diagnostic>, dropped: &mut Vec<Diagnostic>) {
+    let base = Table::try_from(Config::default()).unwrap_or_default();
+    let mut removed: Vec<(Vec<String>, usize)> = Vec::new();
+    let mut reported: Vec<Vec<String>> = Vec::new();
+    let mut broken: Vec<Vec<String>> = Vec::new();
+    let full = |path: &[String]| prefix.iter().chain(path).cloned().collect::<Vec<_>>();
+
+    loop {
+        let mut merged = base.clone();
+        merge(&mut merged, table.clone());
+
+        let mut ignored: Vec<Vec<String>> = Vec::new();
+        let mut callback = |path: serde_ignored::Path| {
+            let mut segments = Vec::new();
+            ignored_path(&path, &mut segments);
+            ignored.push(segments);
+        };
+        let deserializer = serde_ignored::Deserializer::new(Value::Table(merged), &mut callback);
+        let result: Result<Config, _> = serde_path_to_error::deserialize(deserializer);
+
+        for path in ignored {
+            let path = original_path(&path, &removed);
+            if reported.contains(&path) { continue; }
+            let Some((key, parent)) = path.split_last() else { continue };
+            let section = match parent.is_empty() {
+                true  => String::new(),
+                false => format!(" in [{}]", parent.join(".")),
+            };
+            let suggestion = suggest(key, known_keys(&schema_path(parent)));
+            unknown.push(Diagnostic {
+                location: where_in(layer, &full(&path)),
+                message:  format!("unknown key `{}`{}{}", key, section, did_you_mean(suggestion)),
+            });
+            reported.push(path);
+        }
+
+        let Err(e) = result else { return };
+        let path = segments(e.path());
+        let original = original_path(&path, &removed);
+        // label = 5: после удаления label то же место скажет "missing field", это уже сказано
+        let echo = e.inner().message().starts_with("missing field")
+            && broken.iter().any(|b| b.starts_with(&original) && b.len() == original.len() + 1);
+        if !echo {
+            dropped.push(Diagnostic {
+                location: where_in(layer, &full(&original)),
+                message:  match original.is_empty() {
+                    true  => e.inner().message().to_string(),
+                    false => format!("{}: {}", original.join("."), e.inner().message()),
+                },
+            });
+        }
+        broken.push(original.clone());
+
+        if !remove_path(table, &path) { return; }
+        if let Some((index, parent)) = original.split_last() && let Ok(index) = index.parse() {
+            removed.push((parent.to_vec(), index));
+        }
+    }
+}
+
+// то, что проверяется не здесь: условия [[when]], theme = "имя" - ссылка на файл темы (это apply_theme)
+fn take_unchecked(table: &mut Table, keys: &[&str]) -> Table {
+    let mut taken = Table::new();
+    for &key in keys {
+        if key == "theme" && !table.get(key).is_some_and(Value::is_str) { continue; }
+        if let Some(value) = table.remove(key) { taken.insert(key.to_string(), value); }
+    }
+    taken
+}
+
+// возвращает слой без сломанных ключей - из таких потом собирается общий конфиг
+fn check_layer(layer: &Layer, unknown: &mut Vec<Diagnostic>, dropped: &mut Vec<Diagnostic>) -> Table {
+    let mut table = layer.table.clone();
+    let when = table.remove("when");
+    let kept = take_unchecked(&mut table, &["theme"]);
+    check_table(layer, &mut table, &[], unknown, dropped);
+    table.extend(kept);
+
+    let Some(Value::Array(blocks)) = when else {
+        table.extend(when.map(|when| ("when".to_string(), when)));
+        return table;
+    };
+    let mut checked = Vec::new();
+    for (i, block) in blocks.into_iter().enumerate() {
+        let Value::Table(mut block) = block else { continue };
+        let kept = take_unchecked(&mut block, &[CONDITIONS, &["name", "theme"]].concat());
+        check_table(layer, &mut block, &["when".to_string(), i.to_string()], unknown, dropped);
+        block.extend(kept);
+        checked.push(Value::Table(block));
+    }
+    table.insert("when".to_string(), Value::Array(checked));
+    table
+}
+
+// только для --check-config и strict: в обычном запуске об этом и так скажут при отрисовке
+fn check_files(config: &Config, problems: &mut Vec<Problem>) {
+    if let Some(file) = &config.ascii.file && !expand_home(file).is_file() {
+        problems.push(Problem::new("ascii.file", format!("ascii art file {} doesn't exist", file)));
+    }
+    if let Some(name) = &config.ascii.name && let Err(e) = find_named_art(name) {
+        problems.push(Problem::new("ascii.name", e));
+    }
+    let uses_image = matches!(config.logo.kind, LogoType::Image | LogoType::ImageAscii);
+    match &config.logo.image {
+        Some(image) if uses_image && !expand_home(image).is_file() =>
+            problems.push(Problem::new("logo.image", format!("image {} doesn't exist", image))),
+        None if uses_image =>
+            problems.push(Problem::new("logo.type", "logo type needs `image = \"path\"` in [logo]")),
+        _ => {}
+    }
+}
+
+// десериализация с путём до сломанного ключа: "categories.2.color: invalid type ..." одной строкой
+pub fn deserialize(table: Table) -> Result<Config, Problem> {
+    serde_path_to_error::deserialize(Value::Table(table)).map_err(|e| {
+        let path = segments(e.path()).join(".");
+        let message = match path.is_empty() {
+            true  => e.inner().message().to_string(),
+            false => format!("{}: {}", path, e.inner().message()),
+        };
+        Problem::new(path, message)
+    })
+}
+
+// старые ключи, которые загрузчик понял сам
+pub fn deprecations(layers: &[Layer]) -> Vec<Diagnostic> {
+    layers.iter()
+        .flat_map(|layer| layer.deprecated.iter().map(move |p| Diagnostic {
+            location: where_in(layer, &split(&p.path)),
+            message:  p.message.clone(),
+        }))
+        .collect()
+}
+
+// слои без сломанных ключей: одно кривое значение не тянет за собой весь конфиг
+pub fn clean_layers(layers: &[Layer], unknown: &mut Vec<Diagnostic>, dropped: &mut Vec<Diagnostic>) -> Vec<Layer> {
+    layers.iter().map(|layer| Layer {
+        origin: layer.origin.clone(),
+        table:  check_layer(layer, unknown, dropped),
+        source: None,
+        deprecated: vec![],
+    }).collect()
+}
+
+// всё, что нашлось: ошибки чтения, лишние ключи и типы по каждому файлу, потом смысловые проверки
+pub fn diagnose(layers: &[Layer], errors: &[String]) -> Vec<Diagnostic> {
+    let mut out: Vec<Diagnostic> = errors.iter()
+        .map(|e| Diagnostic { location: String::new(), message: e.clone() })
+        .collect();
+    out.extend(deprecations(layers));
+
+    let mut dropped = Vec::new();
+    let checked = clean_layers(layers, &mut out, &mut dropped);
+    out.extend(dropped);
+
+    let merged = merge_layers(&checked);
+    let mut problems = Vec::new();
+    match build_config(merged, &mut problems) {
+        Ok(config) => check_files(&config, &mut problems),
+        // сломанные ключи уже выкинуты, это то, что видно только в сумме слоёв
+        Err(problem) => problems.push(problem),
+    }
+
+    out.extend(problems.into_iter().map(|p| Diagnostic {
+        location: locate_in_layers(layers, &p.path),
+        message:  p.message,
+    }));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(dotted: &str) -> Vec<String> {
+        split(dotted)
+    }
+
+    fn layer(text: &str) -> Layer {
+        Layer {
+            origin: "config.toml".to_string(),
+            table:  toml::from_str(text).unwrap(),
+            source: Some(text.to_string()),
+            deprecated: vec![],
+        }
+    }
+
+    #[test]
+    fn distance_counts_edits() {
+        assert_eq!(distance("primary", "primary"), 0);
+        assert_eq!(distance("primray", "primary"), 2);
+        assert_eq!(distance("colour", "color"), 1);
+        assert_eq!(distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn suggest_picks_closest_within_limit() {
+        let keys = ["primary", "secondary", "accent"];
+        assert_eq!(suggest("primray", keys), Some("primary"));
+        assert_eq!(suggest("Accent", keys), Some("accent"));
+        assert_eq!(suggest("background", keys), None);
+        assert_eq!(did_you_mean(suggest("secondry", keys)), ", did you mean `secondary`?");
+        assert_eq!(did_you_mean(None), "");
+    }
+
+    #[test]
+    fn locate_finds_nested_keys_and_array_entries() {
+        let source = "language = \"en\"\n\n[theme]\nprimary = \"red\"\n\n[[categories]]\ncategory = \"system\"\n\n[[categories]]\nname  = \"Media\"\ncolor = \"blue\"\n";
+        assert_eq!(locate(source, &path("theme.primary")), Some((4, 1)));
+        assert_eq!(locate(source, &path("categories.1.color")), Some((11, 1)));
+        // ключа нет - ближайший найденный родитель
+        assert_eq!(locate(source, &path("theme.accent")), Some((3, 2)));
+        assert_eq!(locate(source, &path("nothing")), None);
+    }
+
+    #[test]
+    fn original_path_skips_removed_entries() {
+        let removed = vec![(path("categories"), 1)];
+        assert_eq!(original_path(&path("categories.0.color"), &removed), path("categories.0.color"));
+        assert_eq!(original_path(&path("categories.1.color"), &removed), path("categories.2.color"));
+        // выкинули 1, потом ещё раз 1 (бывший 2): нынешний 1 - это исходный 3
+        let removed = vec![(path("categories"), 1), (path("categories"), 2)];
+        assert_eq!(original_path(&path("categories.1"), &removed), path("categories.3"));
+        assert_eq!(original_path(&path("custom_fields.1"), &removed), path("custom_fields.1"));
+    }
+
+    #[test]
+    fn remove_path_removes_keys_and_array_entries() {
+        let mut table: Table = toml::from_str("theme = { primary = \"red\", accent = \"blue\" }\nascii.colors = [\"a\", \"b\", \"c\"]").unwrap();
+        assert!(remove_path(&mut table, &path("theme.primary")));
+        assert!(remove_path(&mut table, &path("ascii.colors.1")));
+        assert!(!remove_path(&mut table, &path("ascii.colors.5")));
+        assert!(!remove_path(&mut table, &path("layout.style")));
+        assert!(!remove_path(&mut table, &[]));
+        assert_eq!(table, toml::from_str("theme = { accent = \"blue\" }\nascii.colors = [\"a\", \"c\"]").unwrap());
+    }
+
+    #[test]
+    fn check_table_reports_every_broken_key() {
+        let layer = layer("[theme]\nprimary = 5\nprimray = \"red\"\n\n[ascii]\ncolors = [1, \"red\", 2]\n\n[[categories]]\ncategory = \"system\"\ncolor = 3\n");
+        let mut table = layer.table.clone();
+        let (mut unknown, mut dropped) = (Vec::new(), Vec::new());
+        check_table(&layer, &mut table, &[], &mut unknown, &mut dropped);
+
+        let unknown: Vec<String> = unknown.iter().map(ToString::to_string).collect();
+        assert_eq!(unknown, ["config.toml:3:1: unknown key `primray` in [theme], did you mean `primary`?"]);
+
+        // после выкинутого colors.0 следующая ошибка - это исходный colors.2, а не colors.1
+        let messages: Vec<&str> = dropped.iter().map(|d| d.message.split(':').next().unwrap()).collect();
+        assert_eq!(messages, ["theme.primary", "ascii.colors.0", "ascii.colors.2", "categories.0.color"]);
+        assert_eq!(dropped[3].location, "config.toml:10:1");
+
+        // что осталось, уже разбирается
+        assert!(!table["theme"].as_table().unwrap().contains_key("primary"));
+        assert_eq!(table["ascii"]["colors"], Value::Array(vec![Value::from("red")]));
+        let mut merged = Table::try_from(Config::default()).unwrap();
+        merge(&mut merged, table);
+        assert!(deserialize(merged).is_ok());
+    }
+}
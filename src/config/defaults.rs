@@ -2,35 +2,36 @@ use crate::config::{BuiltinCategory, CategoryConfig, InfoField};
 
 pub fn default_true()         -> bool   { true }
 pub fn default_ascii_width()  -> usize  { 20 }
-pub fn default_accent_color() -> String { "bright_cyan".to_string() }
+pub fn default_rainbow_frequency() -> f32 { 0.1 }
+pub fn default_min_info_width() -> usize { 30 }
+pub fn default_label_filler()    -> String { ".".to_string() }
+pub fn default_label_separator() -> String { " ".to_string() }
+pub fn default_label_min_width() -> usize  { 14 }
+pub fn default_palette_block()   -> String { "█".to_string() }
+pub fn default_palette_width()   -> usize  { 3 }
+pub fn default_timeout_ms()      -> u64    { 2000 }
+pub fn default_placeholder()     -> String { "N/A".to_string() }
+pub fn default_timeout_placeholder() -> String { "timeout".to_string() }
+pub fn default_palette_rows()    -> Vec<String> { vec!["0-7".to_string(), "8-15".to_string()] }
+
+fn builtin(category: BuiltinCategory, enabled: bool) -> CategoryConfig {
+    CategoryConfig {
+        category:   Some(category),
+        name:       None,
+        icon:       None,
+        color:      None,
+        enabled,
+        fields:     vec![],
+    }
+}
 
 pub fn default_categories() -> Vec<CategoryConfig> {
     vec![
-        CategoryConfig {
-            category:   BuiltinCategory::System,
-            enabled:    true,
-            fields:     vec![],
-        },
-        CategoryConfig {
-            category:   BuiltinCategory::Hardware,
-            enabled:    true,
-            fields:     vec![],
-        },
-        CategoryConfig {
-            category:   BuiltinCategory::Res,
-            enabled:    true,
-            fields:     vec![],
-        },
-        CategoryConfig {
-            category:   BuiltinCategory::Env,
-            enabled:    true,
-            fields:     vec![],
-        },
-        CategoryConfig {
-            category:   BuiltinCategory::Net,
-            enabled:    false,
-            fields:     vec![],
-        },
+        builtin(BuiltinCategory::System,   true),
+        builtin(BuiltinCategory::Hardware, true),
+        builtin(BuiltinCategory::Res,      true),
+        builtin(BuiltinCategory::Env,      true),
+        builtin(BuiltinCategory::Net,      false),
     ]
 }
 
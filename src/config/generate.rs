@@ -0,0 +1,318 @@
+use crate::ascii::builtin::detect_logo;
+use crate::cli::Args;
+use crate::config::defaults::default_fields;
+use crate::config::layers::user_config_path;
+use crate::config::{Config, FieldRef, InfoField};
+use crate::data::collectors::{detect_gpu, read_meminfo};
+use crate::utils::expand_home;
+use std::env;
+use std::fs;
+use toml::{Table, Value};
+
+const HEADER: &str = "\
+============================================================================
+rushfetch - config.toml, written by `rushfetch --gen-config`
+Location: ~/.config/rushfetch/config.toml  (respects $XDG_CONFIG_HOME)
+============================================================================
+
+Every value below is the built-in default, so delete whatever you don't
+change. Settings are merged key by key, each layer overriding the ones before:
+  built-in defaults
+  /etc/rushfetch/config.toml
+  ~/.config/rushfetch/config.toml
+  --config FILE (or RUSHFETCH_CONFIG=FILE)
+  RUSHFETCH_* variables, `__` between sections: RUSHFETCH_LAYOUT__STYLE=box
+  --set KEY=VALUE flags: rushfetch --set labels.filler=- --set palette.enabled=true
+Tables merge, lists ([[categories]], [[custom_fields]]...) are replaced whole.
+
+`rushfetch --check-config` checks this file, `rushfetch --print-config` shows
+the merged result, `rushfetch --explain-config` lists the files and [[when]]
+blocks that apply.
+
+Split the config across files - paths are relative to this one, each file
+overrides what it includes:
+include = [\"colors.toml\", \"~/dotfiles/rushfetch-fields.toml\"]";
+
+// описание каждого ключа верхнего уровня, в порядке вывода. значения берутся из Config::default(),
+// так что пример и код не разъезжаются; ключ без описания всё равно попадёт в файл, в конец
+const SECTIONS: &[(&str, &str)] = &[
+    ("config_version", "\
+Schema version of this file; `rushfetch migrate-config` upgrades older ones."),
+
+    ("strict", "\
+true - any problem `--check-config` would report stops the run instead of
+being a warning (also RUSHFETCH_STRICT=true)."),
+
+    ("language", "\
+--- Language ---------------------------------------------------------
+\"auto\" (from LC_ALL / LC_MESSAGES / LANG) or a code: en ru de es fr uk zh ja;
+\"english\", \"russian\" and so on work too. Your own or fixed-up labels go to
+~/.config/rushfetch/locales/<code>.toml as [categories] and [fields] keyed by id
+(system, resources, custom... / os, memory, local_ip...), missing ones stay English."),
+
+    ("show_icons", "\
+Set to false if your terminal doesn't support Nerd Fonts"),
+
+    ("theme", "\
+--- Color Theme ------------------------------------------------------
+Colors: black red green yellow blue magenta cyan white
+        bright_black bright_red bright_green bright_yellow
+        bright_blue bright_magenta bright_cyan bright_white
+        or \"#rrggbb\" (truecolor, approximated on 16-color terminals)
+
+primary   - category headers and icons
+secondary - field labels (the dotted part)
+accent    - user@host header
+text      - field values
+separator - the line under user@host
+
+Instead of the table, a theme can be taken by name or path:
+theme = \"nord\"
+Built-in: green gold gruvbox nord tokyo-night dracula solarized monokai,
+own ones go to ~/.config/rushfetch/themes/<name>.toml."),
+
+    ("icons", "\
+--- Icons ------------------------------------------------------------
+Category icons, \"\" hides one:
+[icons]
+system      = \"󰍛\"
+hardware    = \"󰘚\"
+resources   = \"󰓅\"
+environment = \"󰆍\"
+network     = \"󰀂\"
+custom      = \"󰆾\""),
+
+    ("ascii", "\
+--- ASCII Art --------------------------------------------------------
+The built-in logo is detected from /etc/os-release; `distro` picks another one
+(add -small or -tiny for a smaller variant: \"arch-small\").
+size: auto | large | small | tiny - auto takes the largest that fits.
+width - minimal width of the logo column.
+Own art: file = \"~/art.txt\", or name = \"mylogo\" from ~/.config/rushfetch/ascii/.
+color = \"blue\" paints the whole art, colors = [\"blue\", \"white\"] is the
+palette for ${c1}..${c9} markers inside it.
+Gradient instead of fixed colors:
+[ascii.gradient]
+mode      = \"vertical\"   # vertical | horizontal | diagonal | rainbow
+stops     = [\"#89b4fa\", \"#cba6f7\", \"#f38ba8\"]
+fallback  = [\"blue\", \"magenta\", \"red\"]   # bands without truecolor
+frequency = 0.1          # rainbow only"),
+
+    ("logo", "\
+--- Logo -------------------------------------------------------------
+type: ascii | image | image-ascii | none
+image = \"~/Pictures/logo.png\" for image and image-ascii (png or jpeg).
+protocol: auto | kitty | iterm2 | sixel; without graphics the ASCII logo is used.
+style (image-ascii): half-block | braille | ramp, ramp = \" .:-=+*#%@\"
+width / height in terminal cells, set one to keep the aspect ratio.
+position: left | right | top | bottom, align: top | center | bottom,
+gap = 3 between logo and info, padding = { top = 1, left = 1 } or a number."),
+
+    ("layout", "\
+--- Layout -----------------------------------------------------------
+overflow: truncate | wrap | none - what to do with lines wider than the terminal
+narrow: stack | hide | keep - when the info is squeezed under min_info_width
+style: plain | box, box: info | category, border: rounded | square | ascii"),
+
+    ("labels", "\
+--- Labels -----------------------------------------------------------
+filler fills the gap after a label (\"\" - no alignment), separator goes
+between the label column and the value; max_width = 24 cuts longer labels."),
+
+    ("palette", "\
+--- Palette ----------------------------------------------------------
+Rows of the terminal's 16 colors under the info.
+rows are ranges of color numbers, align: left | fields | center"),
+
+    ("format", "\
+--- Value formats ----------------------------------------------------
+{key} inserts a value, {key:.1} or {key:GiB.1} formats it,
+<red>, <#ff8800>, <bold> ... </> color parts of it.
+Keys: memory, swap, disk - used, total, free, percent;
+      uptime - days, hours, minutes, seconds; the rest - value
+[format]
+memory = \"{used:GiB.1} / {total:GiB.0} (<bold>{percent}</>%)\"
+uptime = \"{days}d {hours}h\""),
+
+    ("commands", "\
+--- Commands ---------------------------------------------------------
+Defaults for custom fields: a slower command is killed and shows
+timeout_placeholder, a failed or silent one - placeholder."),
+
+    ("cache", "\
+--- Cache ------------------------------------------------------------
+Values cached in ~/.cache/rushfetch; background = true shows an expired
+value and refreshes it for the next run. Built-in fields:
+[cache.ttl]
+gpu       = \"1d\"
+public_ip = \"1h\""),
+
+    ("categories", "\
+--- Categories -------------------------------------------------------
+category - system | hardware | resources | environment | network
+name     - your own category instead (or a new title for a built-in one)
+fields   - built-in fields and custom fields (by name or label), in order
+icon, color - override the icon and theme.primary for this category
+Available fields:
+  system:      os, kernel, arch
+  hardware:    host, cpu, gpu
+  resources:   memory, swap, disk
+  environment: uptime, shell, terminal, de
+  network:     local_ip, public_ip"),
+
+    ("custom_fields", "\
+--- Custom Fields ----------------------------------------------------
+Each field takes its value from exactly one source:
+command (shell), exec = [\"prog\", \"arg\"], env = \"VAR\" or file = \"/path\".
+regex, max_lines, map, colors, hide_if_empty, cache_ttl... are described
+in /etc/rushfetch/config.toml.
+[[custom_fields]]
+name      = \"packages\"
+label     = \"Packages\"
+command   = \"pacman -Q | wc -l\"
+cache_ttl = \"1h\""),
+];
+
+const FOOTER: &str = "\
+--- Conditional Blocks -----------------------------------------------
+[[when]]
+hostname = \"*-server\"   # also os, virtualization, ssh, env
+logo.type = \"none\"";
+
+fn comment(text: &str) -> String {
+    text.lines()
+        .map(|line| if line.is_empty() { "#\n".to_string() } else { format!("# {}\n", line) })
+        .collect()
+}
+
+// пустые таблицы и списки в файле только мешают - их покажет описание
+fn prune(value: &mut Value) -> bool {
+    match value {
+        Value::Table(table) => {
+            table.retain(|_, v| !prune(v));
+            table.is_empty()
+        }
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+// "key = value" в одной таблице - в колонку
+fn align(text: &str) -> String {
+    let mut out = String::new();
+    for block in text.split_inclusive("\n\n") {
+        let width = block.lines()
+            .filter_map(|l| l.split_once(" = ").map(|(key, _)| key.len()))
+            .max().unwrap_or(0);
+        for line in block.split_inclusive('\n') {
+            match line.split_once(" = ") {
+                Some((key, value)) if !line.starts_with('[') => out.push_str(&format!("{:<width$} = {}", key, value)),
+                _ => out.push_str(line),
+            }
+        }
+    }
+    out
+}
+
+fn render(key: &str, value: Value) -> String {
+    let table = Table::from_iter([(key.to_string(), value)]);
+    align(&toml::to_string(&table).unwrap_or_default())
+}
+
+// с описаниями - для --gen-config, без - для --print-config
+pub fn render_config(config: &Config, docs: bool) -> String {
+    let mut values = match Value::try_from(config) {
+        Ok(Value::Table(table)) => table,
+        _ => Table::new(),
+    };
+    values.retain(|_, v| !prune(v));
+
+    let mut out = String::new();
+    if docs { out.push_str(&comment(HEADER)); }
+
+    for (key, doc) in SECTIONS {
+        let value = values.remove(*key).map(|value| render(key, value)).unwrap_or_default();
+        if !docs && value.is_empty() { continue; }
+        // без описаний простые ключи идут подряд, пустая строка только перед таблицами
+        if !out.is_empty() && (docs || value.starts_with('[')) { out.push('\n'); }
+        if docs { out.push_str(&comment(doc)); }
+        out.push_str(&value);
+    }
+    for (key, value) in values {
+        out.push('\n');
+        out.push_str(&render(&key, value));
+    }
+
+    if docs {
+        out.push('\n');
+        out.push_str(&comment(FOOTER));
+    }
+    out
+}
+
+// встроенные категории с явным списком полей: --detect выкидывает то, чего тут нет
+fn fill_fields(config: &mut Config, detect: bool) {
+    let gpu  = !detect || detect_gpu().is_some();
+    let swap = !detect || read_meminfo().3 > 0;
+    let de   = !detect || ["XDG_CURRENT_DESKTOP", "DESKTOP_SESSION"].iter().any(|v| env::var_os(v).is_some());
+
+    for cat in &mut config.categories {
+        let Some(category) = cat.category else { continue };
+        if !cat.fields.is_empty() { continue; }
+        cat.fields = default_fields(category).iter()
+            .filter(|&&f| match f {
+                InfoField::Gpu  => gpu,
+                InfoField::Swap => swap,
+                InfoField::De   => de,
+                _ => true,
+            })
+            .map(|&f| FieldRef::Builtin(f))
+            .collect();
+    }
+
+    if detect && let Some(logo) = detect_logo() {
+        config.ascii.distro = logo.names.first().map(|name| name.to_string());
+    }
+}
+
+// --gen-config [FILE]: FILE, или ~/.config/rushfetch/config.toml; "-" - в stdout
+pub fn gen_config(args: &Args) -> bool {
+    let mut config = Config::default();
+    fill_fields(&mut config, args.detect);
+    let text = render_config(&config, true);
+
+    let path = match args.gen_file.as_deref() {
+        Some("-") => {
+            print!("{}", text);
+            return true;
+        }
+        Some(path) => expand_home(path),
+        None => match user_config_path() {
+            Some(path) => path,
+            None => {
+                eprintln!("rushfetch: can't find the config directory, pass the file: rushfetch --gen-config FILE");
+                return false;
+            }
+        },
+    };
+
+    if path.exists() && !args.force {
+        eprintln!("rushfetch: {} already exists, add --force to overwrite it", path.display());
+        return false;
+    }
+    let written = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => fs::create_dir_all(dir),
+        _ => Ok(()),
+    }.and_then(|_| fs::write(&path, text));
+
+    match written {
+        Ok(()) => {
+            println!("wrote {}", path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("rushfetch: can't write {}: {}", path.display(), e);
+            false
+        }
+    }
+}
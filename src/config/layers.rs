@@ -0,0 +1,248 @@
+use crate::cli::Args;
+use crate::config::check::Problem;
+use crate::config::migrate;
+use crate::utils::{config_home, expand_home};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+// слои сливаются по ключам, каждый следующий перекрывает предыдущие:
+// встроенные значения (Config::default, их подкладывает build_config) -> /etc -> $XDG_CONFIG_HOME -> --config -> RUSHFETCH_* -> --set
+const SYSTEM_CONFIG: &str = "/etc/rushfetch/config.toml";
+
+// переменные окружения, которые не ключи конфига
+const RESERVED_ENV: &[&str] = &["RUSHFETCH_DEBUG", "RUSHFETCH_CONFIG"];
+
+pub struct Layer {
+    // откуда: путь к файлу, "environment", "--set"
+    pub origin: String,
+    pub table:  Table,
+    // текст файла - по нему --check-config ищет строку и колонку
+    pub source: Option<String>,
+    // старые ключи, которые migrate::upgrade уже переделал - о них предупреждаем
+    pub deprecated: Vec<Problem>,
+}
+
+// таблицы сливаются вглубь, всё остальное (и массивы тоже) заменяется целиком
+pub fn merge(target: &mut Table, from: Table) {
+    for (key, value) in from {
+        match (target.get_mut(&key), value) {
+            (Some(Value::Table(target)), Value::Table(from)) => merge(target, from),
+            (_, value) => { target.insert(key, value); }
+        }
+    }
+}
+
+pub fn user_config_path() -> Option<PathBuf> {
+    config_home().map(|dir| dir.join("rushfetch/config.toml"))
+}
+
+// include = ["colors.toml", "~/dots/fields.toml"]: пути от папки файла,
+// сам файл перекрывает то, что подключил. chain - кто кого подключил, чтобы не зациклиться
+fn read_file(path: &Path, chain: &mut Vec<PathBuf>, layers: &mut Vec<Layer>, errors: &mut Vec<String>) -> Result<(), String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+    let mut table: Table = toml::from_str(&content)
+        .map_err(|e| format!("config parse error in {}: {}", path.display(), e))?;
+
+    let includes = match table.remove("include") {
+        None => vec![],
+        Some(Value::String(file)) => vec![file],
+        Some(Value::Array(files)) => files.into_iter().filter_map(|f| match f {
+            Value::String(file) => Some(file),
+            _ => {
+                errors.push(format!("include in {} must list file paths", path.display()));
+                None
+            }
+        }).collect(),
+        Some(_) => {
+            errors.push(format!("include in {} must be a path or a list of paths", path.display()));
+            vec![]
+        }
+    };
+
+    for file in includes {
+        let included = expand_home(&file);
+        let included = match path.parent() {
+            Some(dir) if included.is_relative() => dir.join(included),
+            _ => included,
+        };
+
+        let id = fs::canonicalize(&included).unwrap_or_else(|_| included.clone());
+        if chain.contains(&id) {
+            errors.push(format!("{} includes {} in a loop, skipping it", path.display(), included.display()));
+            continue;
+        }
+
+        chain.push(id);
+        if let Err(e) = read_file(&included, chain, layers, errors) {
+            errors.push(format!("{} (included from {})", e, path.display()));
+        }
+        chain.pop();
+    }
+
+    layers.push(Layer { origin: path.display().to_string(), table, source: Some(content), deprecated: vec![] });
+    Ok(())
+}
+
+// "a.b.c" + значение -> { a = { b = { c = значение } } }
+fn nested(path: &str, value: Value) -> Option<Table> {
+    let mut keys: Vec<&str> = path.split('.').map(str::trim).collect();
+    if keys.iter().any(|k| k.is_empty()) { return None; }
+
+    let mut value = value;
+    while keys.len() > 1 {
+        let key = keys.pop()?;
+        value = Value::Table(Table::from_iter([(key.to_string(), value)]));
+    }
+    Some(Table::from_iter([(keys[0].to_string(), value)]))
+}
+
+// значение как в toml (true, 3, ["a", "b"]), а если не разбирается - просто строка
+fn parse_value(raw: &str) -> Value {
+    toml::from_str::<Table>(&format!("v = {}", raw)).ok()
+        .and_then(|mut t| t.remove("v"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+// RUSHFETCH_LAYOUT__STYLE=box -> layout.style = "box"
+fn env_layer(errors: &mut Vec<String>) -> Option<Layer> {
+    let mut table = Table::new();
+    let mut vars: Vec<(String, String)> = env::vars()
+        .filter(|(name, _)| name.starts_with("RUSHFETCH_") && !RESERVED_ENV.contains(&name.as_str()))
+        .collect();
+    vars.sort();
+
+    for (name, raw) in vars {
+        let path = name["RUSHFETCH_".len()..].to_lowercase().replace("__", ".");
+        match nested(&path, parse_value(&raw)) {
+            Some(t) => merge(&mut table, t),
+            None    => errors.push(format!("can't map {} to a config key, ignoring it", name)),
+        }
+    }
+
+    (!table.is_empty()).then(|| Layer { origin: "environment (RUSHFETCH_*)".to_string(), table, source: None, deprecated: vec![] })
+}
+
+fn set_layer(set: &[String], errors: &mut Vec<String>) -> Option<Layer> {
+    let mut table = Table::new();
+
+    for item in set {
+        let parsed = item.split_once('=')
+            .and_then(|(key, raw)| nested(key, parse_value(raw.trim())));
+        match parsed {
+            Some(t) => merge(&mut table, t),
+            None    => errors.push(format!("--set {}: expected KEY=VALUE, ignoring it", item)),
+        }
+    }
+
+    (!table.is_empty()).then(|| Layer { origin: "--set".to_string(), table, source: None, deprecated: vec![] })
+}
+
+// все слои по порядку; битый файл пропускаем, остальные работают.
+// ошибки не печатаем сами: в strict и --check-config они идут в общий отчёт
+pub fn load_layers(args: &Args) -> (Vec<Layer>, Vec<String>) {
+    let mut layers = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut files: Vec<(PathBuf, bool)> = vec![(PathBuf::from(SYSTEM_CONFIG), false)];
+    files.extend(user_config_path().map(|p| (p, false)));
+    // --config указан явно - его отсутствие уже ошибка
+    files.extend(args.config.as_deref().map(|p| (expand_home(p), true)));
+
+    for (path, required) in files {
+        if !required && !path.exists() { continue; }
+        let mut chain = vec![fs::canonicalize(&path).unwrap_or_else(|_| path.clone())];
+        if let Err(e) = read_file(&path, &mut chain, &mut layers, &mut errors) {
+            errors.push(e);
+        }
+    }
+
+    layers.extend(env_layer(&mut errors));
+    layers.extend(set_layer(&args.set, &mut errors));
+
+    for layer in &mut layers {
+        layer.deprecated = migrate::upgrade(&mut layer.table);
+    }
+    (layers, errors)
+}
+
+// [[when]] из всех слоёв складываются, а не заменяют друг друга
+pub fn merge_layers(layers: &[Layer]) -> Table {
+    let mut table = Table::new();
+    let mut when = Vec::new();
+
+    for layer in layers {
+        let mut from = layer.table.clone();
+        match from.remove("when") {
+            Some(Value::Array(blocks)) => when.extend(blocks),
+            Some(_) => eprintln!("rushfetch: `when` in {} must be a list of [[when]] blocks, ignoring it", layer.origin),
+            None => {}
+        }
+        merge(&mut table, from);
+    }
+
+    if !when.is_empty() { table.insert("when".to_string(), Value::Array(when)); }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(text: &str) -> Table {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn nested_builds_tables_from_dotted_path() {
+        assert_eq!(nested("layout.style", Value::from("box")), Some(table("layout.style = \"box\"")));
+        assert_eq!(nested("a . b.c", Value::from(1)), Some(table("a.b.c = 1")));
+        assert_eq!(nested("language", Value::from("ru")), Some(table("language = \"ru\"")));
+        // пустой кусок пути - не ключ
+        assert_eq!(nested("layout..style", Value::from(1)), None);
+        assert_eq!(nested("", Value::from(1)), None);
+    }
+
+    #[test]
+    fn parse_value_reads_toml_or_falls_back_to_string() {
+        assert_eq!(parse_value("true"), Value::Boolean(true));
+        assert_eq!(parse_value("3"), Value::Integer(3));
+        assert_eq!(parse_value("[\"a\", \"b\"]"), Value::Array(vec![Value::from("a"), Value::from("b")]));
+        assert_eq!(parse_value("\"quoted\""), Value::from("quoted"));
+        assert_eq!(parse_value("box"), Value::from("box"));
+        assert_eq!(parse_value("#ff0000"), Value::from("#ff0000"));
+    }
+
+    #[test]
+    fn merge_goes_into_tables_and_replaces_arrays() {
+        let mut target = table("theme = { primary = \"red\", secondary = \"blue\" }\nascii.colors = [\"red\", \"blue\"]");
+        merge(&mut target, table("theme = { primary = \"green\" }\nascii.colors = [\"cyan\"]\nstrict = true"));
+        assert_eq!(target, table(
+            "strict = true\ntheme = { primary = \"green\", secondary = \"blue\" }\nascii.colors = [\"cyan\"]",
+        ));
+    }
+
+    #[test]
+    fn include_loop_is_reported_and_skipped() {
+        let dir = env::temp_dir().join(format!("rushfetch-include-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.toml"), "include = \"b.toml\"\nlanguage = \"ru\"\n").unwrap();
+        fs::write(dir.join("b.toml"), "include = [\"a.toml\"]\nstrict = true\n").unwrap();
+
+        let path = dir.join("a.toml");
+        let mut chain = vec![fs::canonicalize(&path).unwrap()];
+        let mut layers = Vec::new();
+        let mut errors = Vec::new();
+        let result = read_file(&path, &mut chain, &mut layers, &mut errors);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_ok());
+        // подключённый идёт раньше - сам файл его перекрывает
+        let tables: Vec<&Table> = layers.iter().map(|l| &l.table).collect();
+        assert_eq!(tables, [&table("strict = true"), &table("language = \"ru\"")]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("in a loop"), "{}", errors[0]);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::cli::Args;
+use crate::config::check::Problem;
+use crate::config::layers::user_config_path;
+use crate::config::BuiltinCategory;
+use crate::utils::expand_home;
+use serde::Deserialize;
+use std::fs;
+use toml::{Table, Value};
+use toml_edit::{DocumentMut, InlineTable, Item, Key};
+
+// версия схемы конфига. 1 - как в README первых версий, без config_version:
+// [[categories]] с label = "system" и subcategories = [...]
+pub const CONFIG_VERSION: u32 = 2;
+
+// старый ключ -> новый; dropped - новый уже задан, старый просто выкидываем
+struct Rename {
+    from:    &'static str,
+    to:      &'static str,
+    dropped: bool,
+}
+
+// label = "system" - встроенная категория, label = "Media" - своя с этим именем
+fn category_renames(has: impl Fn(&str) -> bool, label: Option<&str>) -> Vec<Rename> {
+    let mut renames = Vec::new();
+
+    if has("label") {
+        let builtin = label.is_some_and(|l| BuiltinCategory::deserialize(Value::from(l)).is_ok());
+        let (to, dropped) = match (has("category"), has("name")) {
+            (_, true)      => ("name", true),
+            (true, false)  => ("name", false),
+            (false, false) => (if builtin { "category" } else { "name" }, false),
+        };
+        renames.push(Rename { from: "label", to, dropped });
+    }
+    if has("subcategories") {
+        renames.push(Rename { from: "subcategories", to: "fields", dropped: has("fields") });
+    }
+    renames
+}
+
+fn describe(rename: &Rename) -> String {
+    match rename.dropped {
+        true  => format!("`{}` is ignored, `{}` is already set", rename.from, rename.to),
+        false => format!("`{}` is now `{}`", rename.from, rename.to),
+    }
+}
+
+fn upgrade_categories(table: &mut Table, prefix: &str, problems: &mut Vec<Problem>) {
+    let Some(Value::Array(categories)) = table.get_mut("categories") else { return };
+
+    for (i, entry) in categories.iter_mut().enumerate() {
+        let Value::Table(entry) = entry else { continue };
+        let renames = category_renames(|k| entry.contains_key(k), entry.get("label").and_then(Value::as_str));
+
+        for rename in renames {
+            let Some(value) = entry.remove(rename.from) else { continue };
+            if !rename.dropped { entry.insert(rename.to.to_string(), value); }
+            problems.push(Problem::new(
+                format!("{}categories.{}.{}", prefix, i, rename.from),
+                format!("deprecated key in [[categories]]: {}", describe(&rename)),
+            ));
+        }
+    }
+}
+
+pub const MIGRATE_HINT: &str = "`rushfetch migrate-config` rewrites old keys in place, comments included";
+
+// есть ли что переписывать - а не только слишком новый config_version
+pub fn needs_migration(deprecated: &[Problem]) -> bool {
+    deprecated.iter().any(|p| p.path != "config_version")
+}
+
+// старые ключи понимаем и так, но с предупреждением - чтобы обновление пакета ничего не ломало
+pub fn upgrade(table: &mut Table) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    if let Some(version) = table.get("config_version").and_then(Value::as_integer) && version > CONFIG_VERSION as i64 {
+        problems.push(Problem::new("config_version", format!(
+            "config_version = {} is newer than this rushfetch knows ({}), some keys may be unknown", version, CONFIG_VERSION,
+        )));
+    }
+
+    upgrade_categories(table, "", &mut problems);
+    if let Some(Value::Array(blocks)) = table.get_mut("when") {
+        for (i, block) in blocks.iter_mut().enumerate() {
+            if let Value::Table(block) = block {
+                upgrade_categories(block, &format!("when.{}.", i), &mut problems);
+            }
+        }
+    }
+    problems
+}
+
+// ниже - то же самое, но правкой текста файла: порядок ключей и комментарии остаются на месте
+
+fn rename_in_table(table: &mut toml_edit::Table, renames: &[Rename]) {
+    let keys: Vec<String> = table.iter().map(|(key, _)| key.to_string()).collect();
+    let entries: Vec<(Key, Item)> = keys.iter().filter_map(|key| table.remove_entry(key)).collect();
+
+    for (key, item) in entries {
+        match renames.iter().find(|r| r.from == key.get()) {
+            Some(rename) if rename.dropped => {}
+            Some(rename) => { table.insert_formatted(&Key::new(rename.to).with_leaf_decor(key.leaf_decor().clone()), item); }
+            None         => { table.insert_formatted(&key, item); }
+        }
+    }
+}
+
+fn rename_in_inline(table: &mut InlineTable, renames: &[Rename]) {
+    let keys: Vec<String> = table.iter().map(|(key, _)| key.to_string()).collect();
+    let entries: Vec<(Key, toml_edit::Value)> = keys.iter().filter_map(|key| table.remove_entry(key)).collect();
+
+    for (key, value) in entries {
+        match renames.iter().find(|r| r.from == key.get()) {
+            Some(rename) if rename.dropped => {}
+            Some(rename) => { table.insert_formatted(&Key::new(rename.to).with_leaf_decor(key.leaf_decor().clone()), value); }
+            None         => { table.insert_formatted(&key, value); }
+        }
+    }
+}
+
+fn note(prefix: &str, i: usize, renames: &[Rename], changes: &mut Vec<String>) {
+    for rename in renames {
+        changes.push(format!("{}categories.{}: {}", prefix, i, describe(rename)));
+    }
+}
+
+// categories = [{ ... }]
+fn migrate_inline_categories(value: Option<&mut toml_edit::Value>, prefix: &str, changes: &mut Vec<String>) {
+    let Some(toml_edit::Value::Array(entries)) = value else { return };
+
+    for (i, entry) in entries.iter_mut().enumerate() {
+        let Some(table) = entry.as_inline_table_mut() else { continue };
+        let renames = category_renames(|k| table.contains_key(k), table.get("label").and_then(toml_edit::Value::as_str));
+        note(prefix, i, &renames, changes);
+        rename_in_inline(table, &renames);
+    }
+}
+
+// [[categories]] или тот же массив строкой
+fn migrate_categories(item: Option<&mut Item>, prefix: &str, changes: &mut Vec<String>) {
+    match item {
+        Some(Item::ArrayOfTables(tables)) => for (i, table) in tables.iter_mut().enumerate() {
+            let renames = category_renames(|k| table.contains_key(k), table.get("label").and_then(Item::as_str));
+            note(prefix, i, &renames, changes);
+            rename_in_table(table, &renames);
+        },
+        Some(Item::Value(value)) => migrate_inline_categories(Some(value), prefix, changes),
+        _ => {}
+    }
+}
+
+// rushfetch migrate-config [FILE]: файл из аргумента, --config или ~/.config/rushfetch/config.toml.
+// старый файл остаётся рядом как FILE.bak
+pub fn migrate_config(args: &Args) -> bool {
+    let path = match args.migrate_file.as_deref().or(args.config.as_deref()) {
+        Some(path) => expand_home(path),
+        None => match user_config_path() {
+            Some(path) => path,
+            None => {
+                eprintln!("rushfetch: can't find the config directory, pass the file: rushfetch migrate-config FILE");
+                return false;
+            }
+        },
+    };
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("rushfetch: can't read {}: {}", path.display(), e);
+            return false;
+        }
+    };
+    let mut doc: DocumentMut = match content.parse() {
+        Ok(doc) => doc,
+        Err(e) => {
+            eprintln!("rushfetch: config parse error in {}: {}", path.display(), e);
+            return false;
+        }
+    };
+
+    let version = doc.get("config_version").and_then(Item::as_integer);
+    if version.is_some_and(|v| v > CONFIG_VERSION as i64) {
+        eprintln!("rushfetch: {} is for a newer rushfetch (config_version = {}), leaving it as is", path.display(), version.unwrap_or_default());
+        return false;
+    }
+
+    let mut changes = Vec::new();
+    migrate_categories(doc.get_mut("categories"), "", &mut changes);
+    match doc.get_mut("when") {
+        Some(Item::ArrayOfTables(blocks)) => for (i, block) in blocks.iter_mut().enumerate() {
+            migrate_categories(block.get_mut("categories"), &format!("when.{}.", i), &mut changes);
+        },
+        Some(Item::Value(toml_edit::Value::Array(blocks))) => for (i, block) in blocks.iter_mut().enumerate() {
+            let Some(block) = block.as_inline_table_mut() else { continue };
+            migrate_inline_categories(block.get_mut("categories"), &format!("when.{}.", i), &mut changes);
+        },
+        _ => {}
+    }
+
+    if version != Some(CONFIG_VERSION as i64) {
+        match doc.get_mut("config_version") {
+            Some(item) => *item = toml_edit::value(CONFIG_VERSION as i64),
+            None => {
+                let key = Key::new("config_version");
+                doc.insert_formatted(&key, toml_edit::value(CONFIG_VERSION as i64));
+                if let Some(mut key) = doc.key_mut("config_version") {
+                    key.leaf_decor_mut().set_prefix("\n# schema version, `rushfetch migrate-config` keeps it current\n");
+                }
+            }
+        }
+        changes.push(format!("config_version = {}", CONFIG_VERSION));
+    }
+
+    if changes.is_empty() {
+        println!("{} is already up to date", path.display());
+        return true;
+    }
+
+    let backup = path.with_extension("toml.bak");
+    if let Err(e) = fs::write(&backup, &content).and_then(|_| fs::write(&path, doc.to_string())) {
+        eprintln!("rushfetch: can't write {}: {}", path.display(), e);
+        return false;
+    }
+
+    for change in &changes {
+        println!("{}", change);
+    }
+    println!("migrated {} (the old file is {})", path.display(), backup.display());
+    true
+}
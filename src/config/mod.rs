@@ -1,8 +1,21 @@
+pub mod check;
 pub mod defaults;
+pub mod generate;
+pub mod layers;
+pub mod migrate;
+pub mod themes;
+pub mod when;
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::cli::Args;
+use crate::config::check::{Problem, did_you_mean, names_of, suggest};
+use crate::data::format::Template;
+use crate::localization::Locale;
+use crate::ui::colors::parse_rgb;
+use crate::utils::parse_duration;
 
-#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum InfoField {
     Kernel,    Os,       Arch, Host,
@@ -13,37 +26,456 @@ pub enum InfoField {
     Uptime,
 }
 
+impl InfoField {
+    // имя как в конфиге: "local_ip"
+    pub fn name(self) -> String {
+        toml::Value::try_from(self).ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum BuiltinCategory {
     System, Hardware,
-    Res, Env, Net,
+    #[serde(rename = "resources",   alias = "res")] Res,
+    #[serde(rename = "environment", alias = "env")] Env,
+    #[serde(rename = "network",     alias = "net")] Net,
 }
 
+impl BuiltinCategory {
+    // имя как в конфиге: "resources"
+    pub fn name(self) -> String {
+        toml::Value::try_from(self).ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+}
+
+// поле в категории: встроенное или кастомное по имени
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum FieldRef {
+    Builtin(InfoField),
+    Custom(String),
+}
+
+// встроенная категория (category = "system") или своя (name = "Media")
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CategoryConfig {
-    pub category: BuiltinCategory,
+    pub category: Option<BuiltinCategory>,
+    // у встроенной - переименование
+    pub name:  Option<String>,
+    pub icon:  Option<String>,
+    pub color: Option<String>,
 
     #[serde(default = "defaults::default_true")]
     pub enabled: bool,
     #[serde(default)]
-    pub fields: Vec<InfoField>,
+    pub fields: Vec<FieldRef>,
+}
+
+impl CategoryConfig {
+    // fields, а у встроенной категории без них - поля по умолчанию
+    pub fn field_list(&self) -> Vec<FieldRef> {
+        match self.category {
+            Some(cat) if self.fields.is_empty() =>
+                defaults::default_fields(cat).iter().map(|&f| FieldRef::Builtin(f)).collect(),
+            _ => self.fields.clone(),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CustomField {
+    // по нему (или по label) поле ставится в категорию
+    pub name:    Option<String>,
     pub label:   String,
-    pub command: String,
+    // откуда значение - ровно одно из четырёх, shell только у command
+    pub command: Option<String>,
+    pub exec:    Option<Vec<String>>,
+    pub env:     Option<String>,
+    pub file:    Option<String>,
+    // только строка файла с этим номером, с 1
+    pub line:    Option<usize>,
+    // из вывода любого источника берём совпадение (или первую группу)
+    pub regex:   Option<String>,
+    // regex, собранный в check_custom_fields
+    #[serde(skip)]
+    pub compiled: Option<regex_lite::Regex>,
+    // раскраска команды: strip - выкинуть, keep - оставить как есть, без цвета темы
+    #[serde(default)] pub strip_ansi: bool,
+    #[serde(default)] pub keep_ansi:  bool,
+    // сколько строк вывода показывать, остальные под первой с отступом
+    pub max_lines: Option<usize>,
+    // "0" = "Clean" - подмена всего значения
+    #[serde(default)] pub map: HashMap<String, String>,
+    // первое подошедшее правило красит значение
+    #[serde(default)] pub colors: Vec<ColorRule>,
+    // пустой вывод / ненулевой код выхода, таймаут - строку не показываем вовсе
+    #[serde(default)] pub hide_if_empty:  bool,
+    #[serde(default)] pub hide_if_failed: bool,
+    // свои таймаут и заглушка вместо общих из [commands]
+    pub timeout_ms:  Option<u64>,
+    pub placeholder: Option<String>,
+    // "1h", "30m"... без него команда запускается каждый раз
+    pub cache_ttl:   Option<String>,
+}
+
+// условия складываются; без условий правило подходит всегда
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ColorRule {
+    pub equals: Option<String>,
+    pub regex:  Option<String>,
+    // сравнивается первое число в значении: "83%" -> 83
+    pub above:  Option<f64>,
+    pub below:  Option<f64>,
+    pub color:  String,
+    // regex, собранный в check_custom_fields; без него правило с regex не срабатывает
+    #[serde(skip)]
+    pub compiled: Option<regex_lite::Regex>,
+}
+
+impl ColorRule {
+    pub fn matches(&self, value: &str) -> bool {
+        let number = first_number(value);
+        self.equals.as_ref().is_none_or(|e| e == value)
+            && (self.regex.is_none() || self.compiled.as_ref().is_some_and(|re| re.is_match(value)))
+            && self.above.is_none_or(|a| number.is_some_and(|n| n > a))
+            && self.below.is_none_or(|b| number.is_some_and(|n| n < b))
+    }
+}
+
+// "load 1.5, 2" -> 1.5
+fn first_number(s: &str) -> Option<f64> {
+    let start = s.find(|c: char| c.is_ascii_digit())?;
+    let start = if s[..start].ends_with('-') { start - 1 } else { start };
+    let len = s[start + 1..].find(|c: char| !c.is_ascii_digit() && c != '.').map_or(s.len() - start, |l| l + 1);
+    s[start..start + len].trim_end_matches('.').parse().ok()
+}
+
+pub enum Source<'a> {
+    Command(&'a str),
+    Exec(&'a [String]),
+    Env(&'a str),
+    File(&'a str),
+}
+
+// кэш значений в $XDG_CACHE_HOME/rushfetch
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct CacheConfig {
+    // протухшее показываем сразу, а обновляем в фоне к следующему запуску
+    #[serde(default)] pub background: bool,
+    // встроенные поля: gpu = "1d", public_ip = "1h"
+    #[serde(default)] pub ttl: HashMap<InfoField, String>,
+}
+
+// общие настройки для команд кастомных полей
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CommandsConfig {
+    #[serde(default = "defaults::default_timeout_ms")]          pub timeout_ms:  u64,
+    // команда упала или ничего не вывела
+    #[serde(default = "defaults::default_placeholder")]         pub placeholder: String,
+    #[serde(default = "defaults::default_timeout_placeholder")] pub timeout_placeholder: String,
+}
+
+impl Default for CommandsConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms:          defaults::default_timeout_ms(),
+            placeholder:         defaults::default_placeholder(),
+            timeout_placeholder: defaults::default_timeout_placeholder(),
+        }
+    }
+}
+
+impl CustomField {
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.as_deref() == Some(name) || self.label == name
+    }
+
+    // None - источник не задан или задано несколько, такие поля выкидывает check_custom_fields
+    pub fn source(&self) -> Option<Source<'_>> {
+        let sources = [
+            self.command.as_deref().map(Source::Command),
+            self.exec.as_deref().map(Source::Exec),
+            self.env.as_deref().map(Source::Env),
+            self.file.as_deref().map(Source::File),
+        ];
+        let mut set = sources.into_iter().flatten();
+        match (set.next(), set.next()) {
+            (Some(source), None) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GradientMode {
+    Vertical, Horizontal,
+    Diagonal, Rainbow,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GradientConfig {
+    pub mode: GradientMode,
+
+    // опорные цвета, "#rrggbb" или имена. для rainbow не нужны
+    #[serde(default)] pub stops:    Vec<String>,
+    // цвета полос для терминалов без truecolor
+    #[serde(default)] pub fallback: Vec<String>,
+    #[serde(default = "defaults::default_rainbow_frequency")] pub frequency: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LogoSize {
+    // самый большой вариант, который влезает в терминал
+    #[default]
+    Auto,
+    Large, Small, Tiny,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LogoPosition {
+    #[default]
+    Left, Right,
+    Top,  Bottom,
+}
+
+// где лого по вертикали, если оно ниже или выше инфы
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LogoAlign {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+}
+
+// padding = 2 или padding = { left = 2, top = 1 }
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum Padding {
+    All(usize),
+    Sides {
+        #[serde(default)] top:    usize,
+        #[serde(default)] right:  usize,
+        #[serde(default)] bottom: usize,
+        #[serde(default)] left:   usize,
+    },
+}
+
+impl Padding {
+    // (top, right, bottom, left), как в css
+    pub fn sides(&self) -> (usize, usize, usize, usize) {
+        match *self {
+            Padding::All(n) => (n, n, n, n),
+            Padding::Sides { top, right, bottom, left } => (top, right, bottom, left),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AsciiConfig {
     pub file: Option<String>,
+    // арт по имени из ~/.config/rushfetch/ascii/
+    pub name: Option<String>,
     pub distro: Option<String>,
 
+    // один цвет на весь арт, или палитра для маркеров ${c1}..${c9}
+    pub color:  Option<String>,
+    #[serde(default)] pub colors: Vec<String>,
+    // градиент вместо цветов, маркеры ${cN} тогда игнорируются
+    pub gradient: Option<GradientConfig>,
+
+    #[serde(default)] pub size: LogoSize,
+
     #[serde(default = "defaults::default_true")]          pub enabled: bool,
+    // минимальная ширина колонки под арт
     #[serde(default = "defaults::default_ascii_width")]   pub width:   usize,
-    #[serde(default = "defaults::default_accent_color")]  pub color:   String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogoType {
+    #[default]
+    Ascii,
+    Image,
+    // картинка, перегнанная в символы - для терминалов без графики
+    ImageAscii,
+    None,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImageAsciiStyle {
+    #[default]
+    HalfBlock,
+    Braille,
+    Ramp,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageProtocol {
+    #[default]
+    Auto,
+    Kitty, Iterm2, Sixel,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct LogoConfig {
+    #[serde(default, rename = "type")] pub kind: LogoType,
+
+    // png/jpeg для type = "image" и "image-ascii"
+    pub image: Option<String>,
+    #[serde(default)] pub protocol: ImageProtocol,
+    #[serde(default)] pub style:    ImageAsciiStyle,
+    // символы для style = "ramp", от тёмного к светлому
+    pub ramp: Option<String>,
+    // размер картинки в ячейках терминала, второй считается по пропорциям
+    pub width:  Option<usize>,
+    pub height: Option<usize>,
+
+    // важнее position из заголовка арта
+    pub position: Option<LogoPosition>,
+    // отступы вокруг лого, по умолчанию один пробел слева
+    pub padding:  Option<Padding>,
+    // между лого и инфой: колонки сбоку или строки сверху/снизу
+    pub gap:      Option<usize>,
+    #[serde(default)] pub align: LogoAlign,
+}
+
+// что делать со строкой шире терминала
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Overflow {
+    #[default]
+    Truncate,
+    Wrap,
+    None,
+}
+
+// что делать с лого, если рядом с ним инфе не хватает места
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NarrowMode {
+    #[default]
+    Stack,
+    Hide,
+    Keep,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutStyle {
+    #[default]
+    Plain,
+    Box,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BorderStyle {
+    #[default]
+    Rounded,
+    Square,
+    Ascii,
+}
+
+// одна рамка на всю инфу или своя у каждой категории
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BoxScope {
+    #[default]
+    Info,
+    Category,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LayoutConfig {
+    #[serde(default)] pub overflow: Overflow,
+    #[serde(default)] pub narrow:   NarrowMode,
+    #[serde(default)] pub style:    LayoutStyle,
+    #[serde(default)] pub border:   BorderStyle,
+    #[serde(default, rename = "box")] pub box_scope: BoxScope,
+    // уже этого инфа рядом с лого считается нечитаемой
+    #[serde(default = "defaults::default_min_info_width")] pub min_info_width: usize,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            overflow:       Overflow::default(),
+            narrow:         NarrowMode::default(),
+            style:          LayoutStyle::default(),
+            border:         BorderStyle::default(),
+            box_scope:      BoxScope::default(),
+            min_info_width: defaults::default_min_info_width(),
+        }
+    }
+}
+
+// колонка меток: "OS ........... value"
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LabelConfig {
+    // чем добивать метку до ширины колонки, "" - не выравнивать
+    #[serde(default = "defaults::default_label_filler")]    pub filler:    String,
+    // между колонкой меток и значением
+    #[serde(default = "defaults::default_label_separator")] pub separator: String,
+    #[serde(default = "defaults::default_label_min_width")] pub min_width: usize,
+    // длиннее - обрезаем с многоточием
+    pub max_width: Option<usize>,
+}
+
+impl Default for LabelConfig {
+    fn default() -> Self {
+        Self {
+            filler:    defaults::default_label_filler(),
+            separator: defaults::default_label_separator(),
+            min_width: defaults::default_label_min_width(),
+            max_width: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PaletteAlign {
+    // с начала колонки инфы, как заголовки категорий
+    #[default]
+    Left,
+    // с отступом, как поля
+    Fields,
+    Center,
+}
+
+// ряды 16 цветов терминала под инфой
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PaletteConfig {
+    #[serde(default)] pub enabled: bool,
+    #[serde(default = "defaults::default_palette_block")] pub block: String,
+    // сколько раз повторить block на один цвет
+    #[serde(default = "defaults::default_palette_width")] pub width: usize,
+    // ряд на строку: "0-7", "8-15", "1-6", "4"
+    #[serde(default = "defaults::default_palette_rows")]  pub rows: Vec<String>,
+    #[serde(default)] pub align: PaletteAlign,
+}
+
+impl Default for PaletteConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            block:   defaults::default_palette_block(),
+            width:   defaults::default_palette_width(),
+            rows:    defaults::default_palette_rows(),
+            align:   PaletteAlign::default(),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -55,27 +487,65 @@ pub struct Theme {
     pub separator:  String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
-#[serde(rename_all = "lowercase")]
-pub enum Language {
-    English, Russian,
-    // TODO: добавить "йазыг падонкафф"
+// иконки категорий, None - берём встроенную из localization
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct IconSet {
+    pub system:      Option<String>,
+    pub hardware:    Option<String>,
+    pub resources:   Option<String>,
+    pub environment: Option<String>,
+    pub network:     Option<String>,
+    pub custom:      Option<String>,
+}
+
+impl IconSet {
+    pub fn category(&self, category: BuiltinCategory) -> Option<&str> {
+        match category {
+            BuiltinCategory::System     => self.system.as_deref(),
+            BuiltinCategory::Hardware   => self.hardware.as_deref(),
+            BuiltinCategory::Res        => self.resources.as_deref(),
+            BuiltinCategory::Env        => self.environment.as_deref(),
+            BuiltinCategory::Net        => self.network.as_deref(),
+        }
+    }
+}
+
+// "auto", код ("de", "pt_BR") или имя ("russian"); переводы в locales/, см. localization/locale.rs
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct Language(pub String);
+
+impl Default for Language {
+    fn default() -> Self {
+        Self("english".to_string())
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Config {
+    // см. migrate.rs; без него конфиг считается написанным по старой схеме
+    pub config_version: Option<u32>,
     #[serde(default)] pub language: Language,
     #[serde(default)] pub theme:    Theme,
+    #[serde(default)] pub icons:    IconSet,
     #[serde(default)] pub ascii:    AsciiConfig,
+    #[serde(default)] pub logo:     LogoConfig,
+    #[serde(default)] pub layout:   LayoutConfig,
+    #[serde(default)] pub labels:   LabelConfig,
+    #[serde(default)] pub palette:  PaletteConfig,
+    // шаблоны значений полей, см. data/format.rs
+    #[serde(default)] pub format:   HashMap<InfoField, String>,
+    // они же разобранные, заполняет check_formats
+    #[serde(skip)] pub templates:   HashMap<InfoField, Template>,
     #[serde(default)] pub custom_fields: Vec<CustomField>,
+    #[serde(default)] pub commands: CommandsConfig,
+    #[serde(default)] pub cache:    CacheConfig,
     #[serde(default = "defaults::default_true")]       pub show_icons: bool,
     #[serde(default = "defaults::default_categories")] pub categories: Vec<CategoryConfig>,
-}
-
-impl Default for Language {
-    fn default() -> Self {
-        Language::English
-    }
+    // любая проблема в конфиге - ошибка и выход, см. --check-config
+    #[serde(default)] pub strict: bool,
+    // перевод для language, загружает check_language - один раз на запуск
+    #[serde(skip)] pub locale: Locale,
 }
 
 impl Default for Theme {
@@ -95,9 +565,13 @@ impl Default for AsciiConfig {
         Self {
             enabled: true,
             file:   None,
+            name:   None,
             distro: None,
             width:  20,
-            color:  "bright_cyan".to_string(),
+            size:   LogoSize::Auto,
+            color:  None,
+            colors: vec![],
+            gradient: None,
         }
     }
 }
@@ -105,39 +579,369 @@ impl Default for AsciiConfig {
 impl Default for Config {
     fn default() -> Self {
         Self {
+            config_version: Some(migrate::CONFIG_VERSION),
             language:       Language::default(),
             theme:          Theme::default(),
+            icons:          IconSet::default(),
             ascii:          AsciiConfig::default(),
+            logo:           LogoConfig::default(),
+            layout:         LayoutConfig::default(),
+            labels:         LabelConfig::default(),
+            palette:        PaletteConfig::default(),
+            format:         HashMap::new(),
+            templates:      HashMap::new(),
             show_icons:     true,
             categories:     defaults::default_categories(),
             custom_fields:  vec![],
+            commands:       CommandsConfig::default(),
+            cache:          CacheConfig::default(),
+            strict:         false,
+            locale:         Locale::default(),
         }
     }
 }
 
-pub fn load_config() -> Config {
-    use std::env;
-    use std::fs;
+pub fn load_config(args: &Args) -> Config {
+    let (layers, errors) = layers::load_layers(args);
 
-    let paths: Vec<String> = vec![
-        env::var("HOME")
-            .map(|h| format!("{}/.config/rushfetch/config.toml", h))
-            .unwrap_or_default(),
-        "/etc/rushfetch/config.toml".to_string(),
-    ];
+    // strict: любая мелочь в конфиге - ошибка, а не предупреждение. берём из последнего слоя, где он есть
+    let strict = layers.iter().rev().find_map(|l| l.table.get("strict")).and_then(toml::Value::as_bool);
+    if strict == Some(true) {
+        let diagnostics = check::diagnose(&layers, &errors);
+        if !diagnostics.is_empty() {
+            for diagnostic in &diagnostics {
+                eprintln!("rushfetch: {}", diagnostic);
+            }
+            eprintln!("rushfetch: strict = true, refusing to run with {} config problem(s)", diagnostics.len());
+            std::process::exit(1);
+        }
+    }
+
+    for e in &errors {
+        eprintln!("rushfetch: {}", e);
+    }
+    for deprecated in check::deprecations(&layers) {
+        eprintln!("rushfetch: {}", deprecated);
+    }
+    if layers.iter().any(|l| migrate::needs_migration(&l.deprecated)) {
+        eprintln!("rushfetch: {}", migrate::MIGRATE_HINT);
+    }
+    if layers.is_empty() { return default_config(); }
+
+    // сломанный ключ выкидываем и говорим где, остальное из того же файла работает
+    let mut dropped = Vec::new();
+    let layers = check::clean_layers(&layers, &mut Vec::new(), &mut dropped);
+    for diagnostic in &dropped {
+        eprintln!("rushfetch: {}, ignoring it", diagnostic);
+    }
+
+    let mut problems = Vec::new();
+    let config = build_config(layers::merge_layers(&layers), &mut problems);
+    for problem in &problems {
+        eprintln!("rushfetch: {}", problem.message);
+    }
+
+    match config {
+        Ok(cfg) => cfg,
+        Err(e)  => {
+            eprintln!("rushfetch: config error: {}, using defaults", e.message);
+            default_config()
+        }
+    }
+}
+
+// встроенные значения, но с переводом: его иначе грузит check_language
+fn default_config() -> Config {
+    let mut config = Config::default();
+    config.locale = Locale::load(&config.language).0;
+    config
+}
+
+// --check-config: всё, что не так, с файлом и строкой. false - нашлись проблемы
+pub fn check_config(args: &Args) -> bool {
+    let (layers, errors) = layers::load_layers(args);
+    let diagnostics = check::diagnose(&layers, &errors);
+
+    for diagnostic in &diagnostics {
+        println!("{}", diagnostic);
+    }
+    if layers.iter().any(|l| migrate::needs_migration(&l.deprecated)) {
+        println!("{}", migrate::MIGRATE_HINT);
+    }
+    match (diagnostics.len(), layers.len()) {
+        (0, 0) => println!("no config found, defaults are fine"),
+        (0, n) => println!("config ok ({} layer{})", n, if n == 1 { "" } else { "s" }),
+        (n, _) => println!("{} problem{} found", n, if n == 1 { "" } else { "s" }),
+    }
+    diagnostics.is_empty()
+}
+
+// --explain-config: какие файлы взяты и какие [[when]] сработали
+pub fn explain_config(args: &Args) {
+    let (layers, errors) = layers::load_layers(args);
+    for e in &errors {
+        eprintln!("rushfetch: {}", e);
+    }
+    if layers.is_empty() {
+        println!("no config found, using defaults");
+    }
+    for (i, layer) in layers.iter().enumerate() {
+        println!("layer {}: {}", i + 1, layer.origin);
+    }
+
+    let facts = when::Facts::detect();
+    println!("detected: {}", facts.describe());
+
+    let reports = when::apply_when(&mut layers::merge_layers(&layers), &facts);
+    if reports.is_empty() { println!("no [[when]] blocks"); }
+    for report in reports {
+        println!("[[when]] {}: {}", report.title, if report.applied { "applied" } else { "skipped" });
+        for (condition, matched) in &report.checks {
+            println!("  {} {}", if *matched { "yes" } else { "no " }, condition);
+        }
+        if !report.keys.is_empty() {
+            println!("  overrides: {}", report.keys.join(", "));
+        }
+    }
+}
+
+// слои уже слиты: дальше [[when]], тема, десериализация и проверки.
+// проверки не печатают, а складывают в problems - кто вызвал, тот и решает
+pub fn build_config(mut table: toml::Table, problems: &mut Vec<Problem>) -> Result<Config, Problem> {
+    // до темы: блок может поменять и саму тему
+    if table.contains_key("when") {
+        when::apply_when(&mut table, &when::Facts::detect());
+    }
+
+    if let Err(e) = themes::apply_theme(&mut table) {
+        problems.push(Problem::new("theme", e));
+        table.remove("theme");
+    }
+
+    // встроенные значения - самый нижний слой: неполная [theme] или --set theme.primary=red не ошибка.
+    // подкладываем только после темы - её [ascii] заполняет лишь то, что не задано
+    let mut merged = toml::Table::try_from(Config::default()).unwrap_or_default();
+    layers::merge(&mut merged, table);
+
+    let mut config = check::deserialize(merged)?;
+    check_formats(&mut config, problems);
+    check_cache_ttls(&mut config, problems);
+    check_custom_fields(&mut config, problems);
+    // цвета до check_categories: после неё индексы categories.N уже сдвинуты
+    check_colors(&config, problems);
+    check_categories(&mut config, problems);
+    check_language(&mut config, problems);
+    Ok(config)
+}
+
+fn check_cache_ttls(config: &mut Config, problems: &mut Vec<Problem>) {
+    let mut valid = |path: String, what: &str, ttl: &str| {
+        let ok = parse_duration(ttl).is_some();
+        if !ok {
+            problems.push(Problem::new(path, format!("bad cache_ttl \"{}\" for {}, expected like \"30s\", \"5m\", \"1h\", \"2d\"", ttl, what)));
+        }
+        ok
+    };
+
+    for (i, field) in config.custom_fields.iter_mut().enumerate() {
+        if field.cache_ttl.as_deref().is_some_and(|ttl| !valid(format!("custom_fields.{}.cache_ttl", i), &field.label, ttl)) {
+            field.cache_ttl = None;
+        }
+    }
+    config.cache.ttl.retain(|field, ttl| valid(format!("cache.ttl.{}", field.name()), &field.name(), ttl));
+}
+
+// поле без источника или с кривой регуляркой пропускаем, остальные работают
+fn check_custom_fields(config: &mut Config, problems: &mut Vec<Problem>) {
+    let categories = &config.categories;
+    let mut index = 0;
+    config.custom_fields.retain_mut(|field| {
+        let path = format!("custom_fields.{}", index);
+        index += 1;
+
+        // регэкспы собираем один раз здесь, а не на каждое значение
+        let regex_error = match field.regex.as_deref().map(regex_lite::Regex::new) {
+            Some(Ok(re)) => { field.compiled = Some(re); None }
+            Some(Err(e)) => Some(e),
+            None         => None,
+        };
+        let rule_error = field.colors.iter_mut()
+            .find_map(|rule| match rule.regex.as_deref().map(regex_lite::Regex::new)? {
+                Ok(re) => { rule.compiled = Some(re); None }
+                Err(e) => Some(e),
+            });
+
+        // name = "os" не достать из категории: "os" - встроенное поле
+        let builtin_name = field.name.as_deref().filter(|name| names_of::<InfoField>().contains(name));
+        let problem = match field.source() {
+            None => Some("needs exactly one of command, exec, env or file".to_string()),
+            Some(Source::Exec([])) => Some("exec is empty".to_string()),
+            Some(_) if field.line.is_some() && field.file.is_none() => Some("line only works with file".to_string()),
+            Some(_) if field.line == Some(0) => Some("line counts from 1".to_string()),
+            Some(_) if field.max_lines == Some(0) => Some("max_lines must be at least 1".to_string()),
+            Some(_) if field.strip_ansi && field.keep_ansi => Some("can't both strip_ansi and keep_ansi".to_string()),
+            Some(_) if let Some(name) = builtin_name => Some(format!("is named \"{}\" like a built-in field, so categories can't refer to it", name)),
+            Some(_) => regex_error.or(rule_error)
+                .map(|e| format!("bad regex: {}", e))
+                .or_else(|| field.colors.iter()
+                    .find(|r| parse_rgb(&r.color).is_none())
+                    .map(|r| format!("has unknown color \"{}\"", r.color))),
+        };
+
+        if let Some(problem) = &problem {
+            problems.push(Problem::new(path, format!("custom field \"{}\" {}, skipping it", field.label, problem)));
+            return false;
+        }
+
+        // в блок Custom такое поле уже не попадёт, а категория выключена - его не видно нигде
+        let listed = |enabled: bool| categories.iter()
+            .filter(|c| c.enabled == enabled)
+            .flat_map(|c| &c.fields)
+            .any(|f| matches!(f, FieldRef::Custom(name) if field.matches(name)));
+        if listed(false) && !listed(true) {
+            problems.push(Problem::new(path, format!("custom field \"{}\" is only listed in disabled categories, it won't be shown", field.label)));
+        }
+        true
+    });
+}
+
+// категория без category и name и ссылки на несуществующие кастомные поля - выкидываем
+fn check_categories(config: &mut Config, problems: &mut Vec<Problem>) {
+    let custom_fields = &config.custom_fields;
+    let known: Vec<&str> = names_of::<InfoField>().iter().copied()
+        .chain(custom_fields.iter().flat_map(|c| c.name.as_deref().into_iter().chain([c.label.as_str()])))
+        .collect();
+
+    let mut seen: Vec<String> = Vec::new();
+    let mut index = 0;
+    config.categories.retain_mut(|cat| {
+        let path = format!("categories.{}", index);
+        index += 1;
+
+        let title = match (&cat.name, cat.category) {
+            (Some(name), _)     => name.clone(),
+            (None, Some(cat))   => cat.name(),
+            (None, None) => {
+                problems.push(Problem::new(path, "a [[categories]] entry needs `category` or `name`, skipping it"));
+                return false;
+            }
+        };
+
+        if seen.contains(&title) {
+            problems.push(Problem::new(path.clone(), format!("category \"{}\" is listed more than once", title)));
+        }
+        seen.push(title.clone());
 
-    for path in &paths {
-        if path.is_empty() { continue; }
+        // своей категории поля брать неоткуда, кроме fields
+        if cat.category.is_none() && cat.fields.is_empty() {
+            problems.push(Problem::new(path.clone(), format!("category \"{}\" has no `fields` and isn't built-in, it will be empty", title)));
+        }
+
+        let mut listed: Vec<FieldRef> = Vec::new();
+        let mut field_index = 0;
+        cat.fields.retain(|field| {
+            let field_path = format!("{}.fields.{}", path, field_index);
+            field_index += 1;
+
+            if listed.contains(field) {
+                problems.push(Problem::new(field_path.clone(), format!("field {} is listed twice in category \"{}\"", field_name(field), title)));
+            }
+            listed.push(field.clone());
 
-        if let Ok(content) = fs::read_to_string(path) {
-            match toml::from_str::<Config>(&content) {
-                Ok(cfg) => return cfg,
-                Err(e)  => {
-                    eprintln!("rushfetch: config parse error in {}: {}", path, e);
+            match field {
+                FieldRef::Custom(name) if !custom_fields.iter().any(|c| c.matches(name)) => {
+                    problems.push(Problem::new(field_path, format!(
+                        "unknown field \"{}\" in categories, it's neither built-in nor a custom field's name{}",
+                        name, did_you_mean(suggest(name, known.iter().copied())),
+                    )));
+                    false
                 }
+                _ => true,
+            }
+        });
+        true
+    });
+}
+
+fn field_name(field: &FieldRef) -> String {
+    match field {
+        FieldRef::Builtin(f) => format!("\"{}\"", f.name()),
+        FieldRef::Custom(s)  => format!("\"{}\"", s),
+    }
+}
+
+// неизвестный язык и кривые файлы переводов: рисуем тогда по-английски, но говорим об этом
+fn check_language(config: &mut Config, problems: &mut Vec<Problem>) {
+    let (locale, errors) = Locale::load(&config.language);
+    config.locale = locale;
+    problems.extend(errors.into_iter().map(|e| Problem::new("language", e)));
+}
+
+// неизвестный цвет молча становится белым - лучше сказать
+fn check_colors(config: &Config, problems: &mut Vec<Problem>) {
+    let theme = &config.theme;
+    let ascii = &config.ascii;
+    let mut colors: Vec<(String, &str)> = vec![
+        ("theme.primary".to_string(),   &theme.primary),
+        ("theme.secondary".to_string(), &theme.secondary),
+        ("theme.accent".to_string(),    &theme.accent),
+        ("theme.text".to_string(),      &theme.text),
+        ("theme.separator".to_string(), &theme.separator),
+    ];
+    colors.extend(ascii.color.as_deref().map(|c| ("ascii.color".to_string(), c)));
+    colors.extend(ascii.colors.iter().enumerate().map(|(i, c)| (format!("ascii.colors.{}", i), c.as_str())));
+    if let Some(gradient) = &ascii.gradient {
+        colors.extend(gradient.stops.iter().enumerate().map(|(i, c)| (format!("ascii.gradient.stops.{}", i), c.as_str())));
+        colors.extend(gradient.fallback.iter().enumerate().map(|(i, c)| (format!("ascii.gradient.fallback.{}", i), c.as_str())));
+    }
+    for (i, cat) in config.categories.iter().enumerate() {
+        colors.extend(cat.color.as_deref().map(|c| (format!("categories.{}.color", i), c)));
+    }
+
+    for (path, color) in colors {
+        if parse_rgb(color).is_none() {
+            problems.push(Problem::new(path.clone(), format!("{}: unknown color \"{}\", expected a name like \"bright_cyan\" or \"#rrggbb\"", path, color)));
+        }
+    }
+}
+
+// кривой шаблон не ломает весь конфиг - поле просто выводится как обычно
+fn check_formats(config: &mut Config, problems: &mut Vec<Problem>) {
+    let templates = &mut config.templates;
+    config.format.retain(|&field, template| {
+        match Template::parse(template).and_then(|t| t.validate(field).map(|()| t)) {
+            Ok(t) => {
+                templates.insert(field, t);
+                true
+            }
+            Err(e) => {
+                problems.push(Problem::new(format!("format.{}", field.name()), format!("format.{}: {}, using the default format", field.name(), e)));
+                false
             }
         }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_theme_keeps_other_defaults() {
+        let mut problems = Vec::new();
+        let table = toml::from_str("theme.primary = \"red\"").unwrap();
+        let Ok(config) = build_config(table, &mut problems) else { panic!("partial [theme] must build") };
+        assert_eq!(config.theme.primary, "red");
+        assert_eq!(config.theme.secondary, Theme::default().secondary);
+        assert!(problems.is_empty());
     }
 
-    Config::default()
+    #[test]
+    fn type_error_names_the_key_on_one_line() {
+        let table = toml::from_str("[[categories]]\ncategory = \"system\"\ncolor = 5").unwrap();
+        let Err(problem) = build_config(table, &mut Vec::new()) else { panic!("color = 5 must fail") };
+        assert_eq!(problem.path, "categories.0.color");
+        assert!(problem.message.starts_with("categories.0.color: invalid type"), "{}", problem.message);
+        assert!(!problem.message.contains('\n'));
+    }
 }
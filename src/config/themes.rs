@@ -0,0 +1,123 @@
+use crate::config::{GradientConfig, IconSet, Theme};
+use crate::utils::{config_home, expand_home};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+// встроенные пресеты, те же файлы что лежат в themes/ репозитория
+const BUILTIN_THEMES: &[(&str, &str)] = &[
+    ("green",       include_str!("../../themes/green.toml")),
+    ("gold",        include_str!("../../themes/gold.toml")),
+    ("gruvbox",     include_str!("../../themes/gruvbox.toml")),
+    ("nord",        include_str!("../../themes/nord.toml")),
+    ("tokyo-night", include_str!("../../themes/tokyo-night.toml")),
+    ("dracula",     include_str!("../../themes/dracula.toml")),
+    ("solarized",   include_str!("../../themes/solarized.toml")),
+    ("monokai",     include_str!("../../themes/monokai.toml")),
+];
+
+const SYSTEM_THEMES_DIR: &str = "/usr/share/rushfetch/themes";
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeAscii {
+    pub color:  Option<String>,
+    pub colors: Option<Vec<String>>,
+    pub gradient: Option<GradientConfig>,
+}
+
+// файл темы: сама тема плюс необязательные цвета арта и набор иконок
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeFile {
+    pub theme: Theme,
+    pub ascii: Option<ThemeAscii>,
+    pub icons: Option<IconSet>,
+}
+
+pub fn theme_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = config_home() {
+        dirs.push(home.join("rushfetch/themes"));
+    }
+    dirs.push(PathBuf::from(SYSTEM_THEMES_DIR));
+    dirs
+}
+
+pub fn builtin_theme_names() -> impl Iterator<Item = &'static str> {
+    BUILTIN_THEMES.iter().map(|(name, _)| *name)
+}
+
+// порядок: путь к файлу как есть -> ~/.config/rushfetch/themes -> /usr/share -> встроенные
+fn find_theme(name: &str) -> Result<(String, String), String> {
+    if name.contains('/') || name.ends_with(".toml") {
+        let path = expand_home(name);
+        return fs::read_to_string(&path)
+            .map(|content| (path.display().to_string(), content))
+            .map_err(|e| format!("theme file {} can't be read: {}", path.display(), e));
+    }
+
+    let mut searched = Vec::new();
+    for dir in theme_dirs() {
+        let path = dir.join(format!("{}.toml", name));
+        if let Ok(content) = fs::read_to_string(&path) {
+            return Ok((path.display().to_string(), content));
+        }
+        searched.push(path.display().to_string());
+    }
+
+    if let Some((_, content)) = BUILTIN_THEMES.iter().find(|(n, _)| *n == name) {
+        return Ok((format!("built-in theme '{}'", name), content.to_string()));
+    }
+
+    Err(format!(
+        "theme '{}' not found (searched {}; built-in themes: {})",
+        name, searched.join(", "),
+        builtin_theme_names().collect::<Vec<_>>().join(", ")
+    ))
+}
+
+pub fn load_theme(name: &str) -> Result<toml::Table, String> {
+    let (origin, content) = find_theme(name)?;
+    let invalid = |e: &dyn std::fmt::Display| {
+        format!("theme '{}' ({}) is invalid: {}", name, origin, e)
+    };
+
+    let table: toml::Table = toml::from_str(&content).map_err(|e| invalid(&e))?;
+    // проверяем структуру целиком, чтобы ошибка указывала на файл темы, а не на конфиг
+    toml::Value::Table(table.clone())
+        .try_into::<ThemeFile>()
+        .map_err(|e| invalid(&e))?;
+
+    Ok(table)
+}
+
+// если `theme` в конфиге строка - подставляем файл темы.
+// ascii и icons из темы идут как значения по умолчанию, конфиг их перекрывает
+pub fn apply_theme(config: &mut toml::Table) -> Result<(), String> {
+    let name = match config.get("theme") {
+        Some(toml::Value::String(name)) => name.clone(),
+        _ => return Ok(()),
+    };
+
+    let mut theme = load_theme(&name)?;
+
+    if let Some(t) = theme.remove("theme") {
+        config.insert("theme".to_string(), t);
+    }
+
+    for section in ["ascii", "icons"] {
+        let Some(toml::Value::Table(from_theme)) = theme.remove(section) else { continue };
+
+        let entry = config.entry(section.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+
+        if let toml::Value::Table(target) = entry {
+            for (key, value) in from_theme {
+                target.entry(key).or_insert(value);
+            }
+        }
+    }
+
+    Ok(())
+}
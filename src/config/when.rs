@@ -0,0 +1,141 @@
+use crate::config::layers::merge;
+use crate::data::collectors::{detect_virtualization, get_os_release_id, read_hostname};
+use crate::utils::glob_match;
+use std::env;
+use toml::{Table, Value};
+
+// [[when]]: условия + любые ключи конфига, которые перекрываются если все условия выполнены.
+// блоки применяются по порядку, последний подошедший побеждает
+pub const CONDITIONS: &[&str] = &["hostname", "os", "virtualization", "ssh", "env"];
+
+// то, с чем сравниваем условия
+pub struct Facts {
+    hostname: String,
+    os:       Option<String>,
+    virt:     Option<(&'static str, String)>,
+    ssh:      bool,
+}
+
+impl Facts {
+    pub fn detect() -> Self {
+        let set = |var: &str| env::var_os(var).is_some_and(|v| !v.is_empty());
+        Self {
+            hostname: read_hostname().unwrap_or_default(),
+            os:       get_os_release_id(),
+            virt:     detect_virtualization(),
+            ssh:      set("SSH_CONNECTION") || set("SSH_CLIENT") || set("SSH_TTY"),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "hostname = \"{}\", os = \"{}\", virtualization = \"{}\", ssh = {}",
+            self.hostname,
+            self.os.as_deref().unwrap_or("unknown"),
+            match &self.virt {
+                Some((kind, name)) => format!("{} ({})", kind, name),
+                None               => "none".to_string(),
+            },
+            self.ssh,
+        )
+    }
+}
+
+pub struct WhenReport {
+    // "#2 \"servers\""
+    pub title:   String,
+    // (условие как в конфиге, выполнено ли)
+    pub checks:  Vec<(String, bool)>,
+    pub applied: bool,
+    // какие ключи перекрыл: "logo.type", "categories"
+    pub keys:    Vec<String>,
+}
+
+fn strings(value: &Value) -> Result<Vec<&str>, String> {
+    match value {
+        Value::String(s) => Ok(vec![s.as_str()]),
+        Value::Array(items) => items.iter()
+            .map(|v| v.as_str().ok_or_else(|| "expected a string or a list of strings".to_string()))
+            .collect(),
+        _ => Err("expected a string or a list of strings".to_string()),
+    }
+}
+
+// из списка подходит любое значение, кроме env - там нужны все
+fn check(key: &str, value: &Value, facts: &Facts) -> Result<bool, String> {
+    match key {
+        "hostname" => Ok(strings(value)?.iter().any(|p| glob_match(p, &facts.hostname))),
+        "os" => Ok(strings(value)?.iter()
+            .any(|id| facts.os.as_deref().is_some_and(|os| os.eq_ignore_ascii_case(id)))),
+        "virtualization" => Ok(strings(value)?.iter().any(|v| match &facts.virt {
+            Some((kind, name)) => v.eq_ignore_ascii_case(kind) || v.eq_ignore_ascii_case(name),
+            None               => v.eq_ignore_ascii_case("none"),
+        })),
+        "ssh" => value.as_bool()
+            .map(|ssh| ssh == facts.ssh)
+            .ok_or_else(|| "expected true or false".to_string()),
+        // "VAR" - задана и не пустая, "!VAR" - наоборот
+        "env" => Ok(strings(value)?.iter().all(|var| match var.strip_prefix('!') {
+            Some(var) => env::var_os(var).is_none_or(|v| v.is_empty()),
+            None      => env::var_os(var).is_some_and(|v| !v.is_empty()),
+        })),
+        _ => unreachable!(),
+    }
+}
+
+fn leaf_keys(table: &Table, prefix: &str, out: &mut Vec<String>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            Value::Table(inner) if !inner.is_empty() => leaf_keys(inner, &path, out),
+            _ => out.push(path),
+        }
+    }
+}
+
+// выкидывает `when` из конфига и применяет подошедшие блоки
+pub fn apply_when(config: &mut Table, facts: &Facts) -> Vec<WhenReport> {
+    let blocks = match config.remove("when") {
+        Some(Value::Array(blocks)) => blocks,
+        Some(_) => {
+            eprintln!("rushfetch: `when` must be a list of [[when]] blocks, ignoring it");
+            return vec![];
+        }
+        None => return vec![],
+    };
+
+    let mut reports = Vec::new();
+    for (i, block) in blocks.into_iter().enumerate() {
+        let Value::Table(mut block) = block else { continue };
+
+        let title = match block.remove("name") {
+            Some(Value::String(name)) => format!("#{} \"{}\"", i + 1, name),
+            _                         => format!("#{}", i + 1),
+        };
+
+        let mut checks = Vec::new();
+        let mut broken = false;
+        for &key in CONDITIONS {
+            let Some(value) = block.remove(key) else { continue };
+            match check(key, &value, facts) {
+                Ok(matched) => checks.push((format!("{} = {}", key, value), matched)),
+                Err(e) => {
+                    eprintln!("rushfetch: [[when]] {}: {}: {}, skipping the block", title, key, e);
+                    broken = true;
+                }
+            }
+        }
+        if block.remove("when").is_some() {
+            eprintln!("rushfetch: [[when]] {}: nested `when` isn't supported, ignoring it", title);
+        }
+
+        let applied = !broken && checks.iter().all(|(_, matched)| *matched);
+        let mut keys = Vec::new();
+        leaf_keys(&block, "", &mut keys);
+        if applied { merge(config, block); }
+
+        reports.push(WhenReport { title, checks, applied, keys });
+    }
+
+    reports
+}
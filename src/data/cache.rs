@@ -0,0 +1,123 @@
+use crate::cli::debug;
+use crate::config::{CustomField, InfoField, Source};
+use crate::utils::cache_home;
+use std::fs;
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// один фоновый обновлятель за раз; лок старше этого считаем брошенным
+const LOCK_STALE: Duration = Duration::from_secs(60);
+
+pub enum Lookup {
+    Fresh(String),
+    Stale(String),
+    Missing,
+}
+
+// ~/.cache/rushfetch/<хэш ключа>: первая строка - когда записано, дальше значение
+pub struct Cache {
+    dir: PathBuf,
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+// fnv-1a: DefaultHasher между версиями раста может поменяться, а имена файлов - нет
+fn hash(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+// меняется команда (или обработка вывода) - меняется ключ, старая запись просто протухнет
+pub fn custom_key(field: &CustomField) -> String {
+    let source = match field.source() {
+        Some(Source::Command(cmd)) => cmd.to_string(),
+        Some(Source::Exec(argv))   => format!("exec\0{}", argv.join("\0")),
+        _ => String::new(),
+    };
+    format!("custom\0{}\0{}\0{}", source, field.regex.as_deref().unwrap_or(""), field.strip_ansi)
+}
+
+pub fn field_key(field: InfoField) -> String {
+    format!("field\0{}", field.name())
+}
+
+impl Cache {
+    pub fn open() -> Option<Self> {
+        cache_home().map(|dir| Self { dir: dir.join("rushfetch") })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}", hash(key)))
+    }
+
+    pub fn get(&self, key: &str, ttl: Duration) -> Lookup {
+        let Ok(content) = fs::read_to_string(self.path(key)) else { return Lookup::Missing };
+        let Some((stamp, value)) = content.split_once('\n') else { return Lookup::Missing };
+        let Ok(stamp) = stamp.parse::<u64>() else { return Lookup::Missing };
+
+        if now().saturating_sub(stamp) < ttl.as_secs() {
+            Lookup::Fresh(value.to_string())
+        } else {
+            Lookup::Stale(value.to_string())
+        }
+    }
+
+    // через временный файл, чтобы параллельный запуск не прочитал половину
+    pub fn put(&self, key: &str, value: &str) {
+        let path = self.path(key);
+        let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+
+        let written = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&tmp, format!("{}\n{}", now(), value)))
+            .and_then(|_| fs::rename(&tmp, &path));
+        if let Err(e) = written {
+            debug(&format!("can't write cache {}: {}", path.display(), e));
+            let _ = fs::remove_file(&tmp);
+        }
+    }
+
+    fn lock_path(&self) -> PathBuf {
+        self.dir.join("refresh.lock")
+    }
+
+    // true - лок наш, можно обновлять
+    pub fn lock(&self) -> bool {
+        let path = self.lock_path();
+        let abandoned = fs::metadata(&path).ok()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age > LOCK_STALE);
+        if abandoned { let _ = fs::remove_file(&path); }
+
+        fs::create_dir_all(&self.dir).is_ok()
+            && fs::OpenOptions::new().write(true).create_new(true).open(&path).is_ok()
+    }
+
+    pub fn unlock(&self) {
+        let _ = fs::remove_file(self.lock_path());
+    }
+
+    // протухшее уже показали - обновляем отдельным процессом, вывод его никому не нужен
+    pub fn refresh_in_background(&self) {
+        if !self.lock() { return; }
+
+        let spawned = std::env::current_exe().and_then(|exe| {
+            // те же аргументы, чтобы обновлялся тот же конфиг
+            Command::new(exe)
+                .args(std::env::args().skip(1))
+                .arg("--refresh-cache")
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .process_group(0)
+                .spawn()
+        });
+        if let Err(e) = spawned {
+            debug(&format!("can't start cache refresh: {}", e));
+            self.unlock();
+        }
+    }
+}
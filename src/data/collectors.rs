@@ -7,26 +7,24 @@ use std::io::{Read, Write};
 fn read_cpu_model_from_proc() -> Option<String> {
     if let Ok(content) = fs::read_to_string("/proc/cpuinfo") {
         for line in content.lines() {
-            if let Some(model_start) = line.find("model name") {
-                if let Some(colon_pos) = line[model_start..].find(':') {
-                    let model_value = &line[model_start + colon_pos + 1..];
-                    let trimmed = model_value.trim();
-                    if !trimmed.is_empty() {
-                        return Some(trimmed.split_whitespace().collect::<Vec<_>>().join(" "));
-                    }
+            if let Some(model_start) = line.find("model name")
+                && let Some(colon_pos) = line[model_start..].find(':') {
+                let model_value = &line[model_start + colon_pos + 1..];
+                let trimmed = model_value.trim();
+                if !trimmed.is_empty() {
+                    return Some(trimmed.split_whitespace().collect::<Vec<_>>().join(" "));
                 }
             }
         }
         
         // для армовских
         for line in content.lines() {
-            if let Some(hardware_start) = line.find("Hardware") {
-                if let Some(colon_pos) = line[hardware_start..].find(':') {
-                    let hardware_value = &line[hardware_start + colon_pos + 1..];
-                    let trimmed = hardware_value.trim();
-                    if !trimmed.is_empty() {
-                        return Some(trimmed.to_string());
-                    }
+            if let Some(hardware_start) = line.find("Hardware")
+                && let Some(colon_pos) = line[hardware_start..].find(':') {
+                let hardware_value = &line[hardware_start + colon_pos + 1..];
+                let trimmed = hardware_value.trim();
+                if !trimmed.is_empty() {
+                    return Some(trimmed.to_string());
                 }
             }
         }
@@ -39,9 +37,9 @@ pub fn read_os_pretty_name() -> Option<String> {
     let os_release = read_file_cow("/etc/os-release")?;
     
     for line in os_release.lines() {
-        if line.starts_with("PRETTY_NAME=") {
+        if let Some(value) = line.strip_prefix("PRETTY_NAME=") {
             return Some(
-                line["PRETTY_NAME=".len()..]
+                value
                     .trim_matches('"')
                     .to_string()
             );
@@ -64,9 +62,9 @@ pub fn get_os_release_id() -> Option<String> {
     let os_release = read_file_cow("/etc/os-release")?;
     
     for line in os_release.lines() {
-        if line.starts_with("ID=") {
+        if let Some(value) = line.strip_prefix("ID=") {
             return Some(
-                line["ID=".len()..]
+                value
                     .trim_matches('"')
                     .to_lowercase()
             );
@@ -76,6 +74,57 @@ pub fn get_os_release_id() -> Option<String> {
     None
 }
 
+// ID_LIKE="ubuntu debian" - родители дистрибутива по порядку
+pub fn get_os_release_id_like() -> Vec<String> {
+    let Some(os_release) = read_file_cow("/etc/os-release") else { return vec![] };
+
+    for line in os_release.lines() {
+        if let Some(value) = line.strip_prefix("ID_LIKE=") {
+            return value
+                .trim_matches('"')
+                .split_whitespace()
+                .map(|s| s.to_lowercase())
+                .collect();
+        }
+    }
+
+    vec![]
+}
+
+// ("container" | "vm", что именно). None - железо
+pub fn detect_virtualization() -> Option<(&'static str, String)> {
+    if Path::new("/.dockerenv").exists() { return Some(("container", "docker".to_string())); }
+    if Path::new("/run/.containerenv").exists() { return Some(("container", "podman".to_string())); }
+    if let Some(name) = fs::read_to_string("/run/systemd/container").ok().filter(|s| !s.trim().is_empty()) {
+        return Some(("container", name.trim().to_string()));
+    }
+
+    let cgroup = fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+    for (marker, name) in [("docker", "docker"), ("kubepods", "kubernetes"), ("lxc", "lxc")] {
+        if cgroup.contains(marker) { return Some(("container", name.to_string())); }
+    }
+
+    let osrelease = fs::read_to_string("/proc/sys/kernel/osrelease").unwrap_or_default().to_lowercase();
+    if osrelease.contains("microsoft") { return Some(("vm", "wsl".to_string())); }
+
+    let dmi = |file: &str| fs::read_to_string(format!("/sys/class/dmi/id/{}", file)).unwrap_or_default().to_lowercase();
+    let dmi = format!("{} {}", dmi("sys_vendor"), dmi("product_name"));
+    for (marker, name) in [
+        ("qemu", "qemu"), ("kvm", "kvm"), ("vmware", "vmware"), ("virtualbox", "virtualbox"),
+        ("innotek", "virtualbox"), ("xen", "xen"), ("virtual machine", "hyperv"),
+        ("amazon ec2", "amazon"), ("google compute", "google"), ("parallels", "parallels"),
+    ] {
+        if dmi.contains(marker) { return Some(("vm", name.to_string())); }
+    }
+
+    // dmi бывает недоступен, а флаг hypervisor в cpuinfo есть почти всегда
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    let flagged = cpuinfo.lines()
+        .filter(|l| l.starts_with("flags"))
+        .any(|l| l.split_whitespace().any(|f| f == "hypervisor"));
+    flagged.then(|| ("vm", "unknown".to_string()))
+}
+
 pub fn read_hostname() -> Option<String> {
     fs::read_to_string("/proc/sys/kernel/hostname")
         .ok().map(|s| s.trim().to_string())
@@ -112,10 +161,9 @@ fn detect_gpu_drm() -> Option<String> {
                 }
   
                 let uevent_path = entry.path().join("device/uevent");
-                if let Ok(ue) = fs::read_to_string(&uevent_path) {
-                    if ue.contains("PCI_ID=") {
-                        return Some("(detected GPU)".to_string());
-                    }
+                if let Ok(ue) = fs::read_to_string(&uevent_path)
+                    && ue.contains("PCI_ID=") {
+                    return Some("(detected GPU)".to_string());
                 }
                 
                 // Если ничего не нашли - это не ГПУ, продолжаем пооиск
@@ -165,12 +213,13 @@ pub fn read_meminfo() -> (u64, u64, u64, u64) {
         }
     }
 
-    let used          = total.saturating_sub(available)      / 1024;
-    let total_mb      = total                                / 1024;
-    let swap_used     = swap_total.saturating_sub(swap_free) / 1024;
-    let swap_total_mb = swap_total                           / 1024;
+    // в /proc/meminfo килобайты, отдаём байты
+    let used          = total.saturating_sub(available)      * 1024;
+    let total         = total                                * 1024;
+    let swap_used     = swap_total.saturating_sub(swap_free) * 1024;
+    let swap_total    = swap_total                           * 1024;
 
-    (used, total_mb, swap_used, swap_total_mb)
+    (used, total, swap_used, swap_total)
 }
 
 pub fn read_disk_root() -> (u64, u64) {
@@ -181,8 +230,8 @@ pub fn read_disk_root() -> (u64, u64) {
     unsafe {
         if libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) == 0 {
             let s = stat.assume_init();
-            let total = s.f_blocks * s.f_frsize / (1024 * 1024 * 1024);
-            let free  = s.f_bfree * s.f_frsize / (1024 * 1024 * 1024);
+            let total = s.f_blocks * s.f_frsize;
+            let free  = s.f_bfree * s.f_frsize;
             (total.saturating_sub(free), total)
         } else { (0, 0) }
     }
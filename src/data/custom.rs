@@ -0,0 +1,167 @@
+use crate::cli::{debug, debug_enabled};
+use crate::config::{Config, CustomField, Source};
+use crate::data::cache::{custom_key, Cache, Lookup};
+use crate::utils::{expand_home, parse_duration, run_command, run_exec, strip_ansi, CommandOutcome};
+use std::env;
+use std::fs;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// что получилось у поля; заглушки и скрытие решает рендер
+#[derive(Debug, Clone, Default)]
+pub enum CustomValue {
+    Value(String),
+    // вывода нет или регулярка не нашла
+    #[default]
+    Empty,
+    // ненулевой код выхода (с выводом, если был) или не запустилось
+    Failed(Option<String>),
+    TimedOut,
+}
+
+// значение из кэша или команда, которая ещё работает
+pub enum Pending {
+    Ready(CustomValue),
+    Running(JoinHandle<CustomValue>),
+}
+
+// то, что надо запускать отдельным процессом
+enum Run {
+    Shell(String),
+    Exec(Vec<String>),
+}
+
+// все команды стартуют сразу, каждая в своём потоке - медленная не держит остальные.
+// env и file читаем на месте, процессы для них не нужны.
+// stale - есть протухшие записи, которые показываем как есть
+pub fn spawn_custom_fields(config: &Config, cache: Option<&Cache>, refresh: bool, stale: &mut bool) -> Vec<Pending> {
+    let defaults = &config.commands;
+
+    config.custom_fields.iter().map(|field| {
+        let run = match field.source() {
+            Some(Source::Command(cmd)) => Run::Shell(cmd.to_string()),
+            Some(Source::Exec(argv))   => Run::Exec(argv.to_vec()),
+            Some(Source::Env(var))     => return Pending::Ready(read_env(field, var)),
+            Some(Source::File(path))   => return Pending::Ready(read_file(field, path)),
+            None                       => return Pending::Ready(CustomValue::Empty),
+        };
+
+        let ttl = field.cache_ttl.as_deref().and_then(parse_duration);
+        if let (Some(cache), Some(ttl), false) = (cache, ttl, refresh) {
+            match cache.get(&custom_key(field), ttl) {
+                Lookup::Fresh(value) => return Pending::Ready(CustomValue::Value(value)),
+                Lookup::Stale(value) if config.cache.background => {
+                    *stale = true;
+                    return Pending::Ready(CustomValue::Value(value));
+                }
+                _ => {}
+            }
+        }
+
+        let field = field.clone();
+        let timeout = Duration::from_millis(field.timeout_ms.unwrap_or(defaults.timeout_ms));
+
+        Pending::Running(thread::spawn(move || {
+            let started = Instant::now();
+            let (outcome, shown) = match &run {
+                Run::Shell(cmd) => (run_command(cmd, timeout), cmd.clone()),
+                Run::Exec(argv) => (run_exec(argv, timeout), argv.join(" ")),
+            };
+            if debug_enabled() { report(&field.label, &shown, &outcome, started.elapsed()); }
+
+            match outcome {
+                CommandOutcome::Done { status, stdout, .. } => match pick(&field, &stdout) {
+                    Some(value) if status.success() => CustomValue::Value(value),
+                    None        if status.success() => CustomValue::Empty,
+                    value => CustomValue::Failed(value),
+                },
+                CommandOutcome::TimedOut { .. } => CustomValue::TimedOut,
+                CommandOutcome::Failed(_)       => CustomValue::Failed(None),
+            }
+        }))
+    }).collect()
+}
+
+// ждём команды, удачный вывод кладём в кэш
+pub fn finish_custom_fields(config: &Config, pending: Vec<Pending>, cache: Option<&Cache>) -> Vec<CustomValue> {
+    pending.into_iter().zip(&config.custom_fields).map(|(pending, field)| {
+        let handle = match pending {
+            Pending::Ready(value)    => return value,
+            Pending::Running(handle) => handle,
+        };
+        // паника в потоке - не повод молча показать заглушку
+        let value = handle.join().unwrap_or_else(|_| {
+            eprintln!("rushfetch: custom field \"{}\" crashed, showing it as failed", field.label);
+            CustomValue::Failed(None)
+        });
+
+        if let CustomValue::Value(text) = &value
+            && field.cache_ttl.is_some()
+            && let Some(cache) = cache
+        {
+            cache.put(&custom_key(field), text);
+        }
+        value
+    }).collect()
+}
+
+fn read_env(field: &CustomField, var: &str) -> CustomValue {
+    match env::var(var) {
+        Ok(value) => pick(field, &value).map_or(CustomValue::Empty, CustomValue::Value),
+        Err(_) => {
+            debug(&format!("custom field \"{}\": ${} is not set", field.label, var));
+            CustomValue::Empty
+        }
+    }
+}
+
+fn read_file(field: &CustomField, path: &str) -> CustomValue {
+    let content = match fs::read_to_string(expand_home(path)) {
+        Ok(content) => content,
+        Err(e) => {
+            debug(&format!("custom field \"{}\": can't read {}: {}", field.label, path, e));
+            return CustomValue::Failed(None);
+        }
+    };
+
+    let text = match field.line {
+        Some(n) => content.lines().nth(n.saturating_sub(1)).unwrap_or(""),
+        None    => &content,
+    };
+    pick(field, text).map_or(CustomValue::Empty, CustomValue::Value)
+}
+
+// регулярка: первая группа, если есть, иначе всё совпадение. Пустое - None
+fn pick(field: &CustomField, text: &str) -> Option<String> {
+    let stripped;
+    let text = if field.strip_ansi {
+        stripped = strip_ansi(text);
+        stripped.as_str()
+    } else { text };
+
+    let text = match &field.compiled {
+        Some(re) => {
+            let Some(caps) = re.captures(text) else {
+                debug(&format!("custom field \"{}\": regex didn't match", field.label));
+                return None;
+            };
+            caps.get(1).or(caps.get(0)).map(|m| m.as_str())?
+        }
+        None => text,
+    };
+
+    Some(text.trim()).filter(|t| !t.is_empty()).map(str::to_string)
+}
+
+fn report(label: &str, command: &str, outcome: &CommandOutcome, took: Duration) {
+    let (result, stderr) = match outcome {
+        CommandOutcome::Done { status, stderr, .. } => (status.to_string(), stderr.as_str()),
+        CommandOutcome::TimedOut { stderr }         => ("timed out, killed".to_string(), stderr.as_str()),
+        CommandOutcome::Failed(e)                   => (format!("failed to start: {}", e), ""),
+    };
+
+    debug(&format!("custom field \"{}\" ({}): {} in {}ms", label, command, result, took.as_millis()));
+    for line in stderr.lines().filter(|l| !l.trim().is_empty()) {
+        debug(&format!("  stderr: {}", line));
+    }
+}
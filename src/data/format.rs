@@ -0,0 +1,286 @@
+use crate::config::InfoField;
+use crate::data::SysData;
+use crate::ui::colors::{colorize, parse_rgb};
+use colored::*;
+
+// шаблоны значений: format.memory = "{used:GiB.1} / {total:GiB.0} (<bold>{percent}</>%)"
+// {key} или {key:spec}, spec - ".N" (знаков после точки) или единица "GiB", "GiB.1"
+// <color>, <bold>, <dim>, <italic>, <underline> ... </> - цвет и стиль
+// {{ }} << - сами символы
+
+pub enum Value {
+    Text(String),
+    Bytes(u64),
+    Number(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Text,
+    Bytes,
+    Number,
+}
+
+const TEXT_KEYS:   &[(&str, Kind)] = &[("value", Kind::Text)];
+const USAGE_KEYS:  &[(&str, Kind)] = &[
+    ("used", Kind::Bytes), ("total", Kind::Bytes), ("free", Kind::Bytes), ("percent", Kind::Number),
+];
+const UPTIME_KEYS: &[(&str, Kind)] = &[
+    ("days", Kind::Number), ("hours", Kind::Number), ("minutes", Kind::Number), ("seconds", Kind::Number),
+];
+
+const UNITS: &[(&str, f64)] = &[
+    ("B",   1.0),
+    ("KB",  1e3),  ("MB",  1e6),  ("GB",  1e9),  ("TB",  1e12),
+    ("KiB", 1024.0),
+    ("MiB", 1024.0 * 1024.0),
+    ("GiB", 1024.0 * 1024.0 * 1024.0),
+    ("TiB", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+];
+
+// какие ключи есть у поля
+pub fn field_keys(field: InfoField) -> &'static [(&'static str, Kind)] {
+    match field {
+        InfoField::Memory | InfoField::Swap | InfoField::Disk => USAGE_KEYS,
+        InfoField::Uptime => UPTIME_KEYS,
+        _ => TEXT_KEYS,
+    }
+}
+
+fn usage(used: u64, total: u64) -> Vec<(&'static str, Value)> {
+    let percent = if total == 0 { 0.0 } else { used as f64 * 100.0 / total as f64 };
+    vec![
+        ("used",    Value::Bytes(used)),
+        ("total",   Value::Bytes(total)),
+        ("free",    Value::Bytes(total.saturating_sub(used))),
+        ("percent", Value::Number(percent)),
+    ]
+}
+
+impl SysData {
+    // значения для шаблона, None - поля нет
+    pub fn values(&self, field: InfoField) -> Option<Vec<(&'static str, Value)>> {
+        match field {
+            InfoField::Memory => Some(usage(self.memory_used, self.memory_total)),
+            InfoField::Swap   => Some(usage(self.swap_used, self.swap_total)),
+            InfoField::Disk   => Some(usage(self.disk_used, self.disk_total)),
+            InfoField::Uptime => {
+                let secs = self.uptime_secs;
+                Some(vec![
+                    ("days",    Value::Number((secs / 86400) as f64)),
+                    ("hours",   Value::Number((secs % 86400 / 3600) as f64)),
+                    ("minutes", Value::Number((secs % 3600 / 60) as f64)),
+                    ("seconds", Value::Number((secs % 60) as f64)),
+                ])
+            }
+            _ => self.get(field).map(|v| vec![("value", Value::Text(v))]),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Spec {
+    Default,
+    Precision(usize),
+    Unit(&'static str, f64, Option<usize>),
+}
+
+#[derive(Debug, Clone)]
+enum Style {
+    Color(String),
+    Bold,
+    Dim,
+    Italic,
+    Underline,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(String),
+    Key { name: String, spec: Spec },
+    Open(Style),
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+fn parse_precision(s: &str) -> Result<usize, String> {
+    s.parse().map_err(|_| format!("bad precision \".{}\"", s))
+}
+
+fn parse_spec(spec: &str) -> Result<Spec, String> {
+    if spec.is_empty() { return Ok(Spec::Default); }
+    if let Some(p) = spec.strip_prefix('.') { return parse_precision(p).map(Spec::Precision); }
+
+    let (unit, precision) = match spec.split_once('.') {
+        Some((unit, p)) => (unit, Some(parse_precision(p)?)),
+        None            => (spec, None),
+    };
+    UNITS.iter()
+        .find(|(name, _)| *name == unit)
+        .map(|&(name, div)| Spec::Unit(name, div, precision))
+        .ok_or_else(|| format!("unknown unit \"{}\", expected one of: {}",
+            unit, UNITS.iter().map(|(n, _)| *n).collect::<Vec<_>>().join(", ")))
+}
+
+fn parse_style(name: &str) -> Result<Style, String> {
+    match name {
+        "bold"      => Ok(Style::Bold),
+        "dim"       => Ok(Style::Dim),
+        "italic"    => Ok(Style::Italic),
+        "underline" => Ok(Style::Underline),
+        _ if parse_rgb(name).is_some() => Ok(Style::Color(name.to_string())),
+        _ => Err(format!("unknown style <{}>", name)),
+    }
+}
+
+impl Template {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+
+        // читаем до закрывающего символа
+        fn until(chars: &mut std::iter::Peekable<std::str::Chars>, end: char) -> Option<String> {
+            let mut out = String::new();
+            for c in chars.by_ref() {
+                if c == end { return Some(out); }
+                out.push(c);
+            }
+            None
+        }
+
+        while let Some(c) = chars.next() {
+            let tag = match c {
+                '{' | '}' | '<' if chars.peek() == Some(&c) => {
+                    chars.next();
+                    literal.push(c);
+                    continue;
+                }
+                '{' => {
+                    let inner = until(&mut chars, '}').ok_or("unclosed {")?;
+                    let (name, spec) = inner.split_once(':').unwrap_or((&inner, ""));
+                    if name.is_empty() { return Err("empty {}".to_string()); }
+                    Segment::Key { name: name.trim().to_string(), spec: parse_spec(spec.trim())? }
+                }
+                '}' => return Err("unmatched }, use }} for a literal one".to_string()),
+                '<' => match until(&mut chars, '>').ok_or("unclosed <, use << for a literal one")?.trim() {
+                    "/"  => Segment::Close,
+                    name => Segment::Open(parse_style(name)?),
+                },
+                _ => {
+                    literal.push(c);
+                    continue;
+                }
+            };
+
+            if !literal.is_empty() { segments.push(Segment::Literal(std::mem::take(&mut literal))); }
+            segments.push(tag);
+        }
+        if !literal.is_empty() { segments.push(Segment::Literal(literal)); }
+
+        Ok(Self { segments })
+    }
+
+    // ключи и единицы сверяем с тем, что поле умеет
+    pub fn validate(&self, field: InfoField) -> Result<(), String> {
+        let keys = field_keys(field);
+
+        for segment in &self.segments {
+            let Segment::Key { name, spec } = segment else { continue };
+            let Some(&(_, kind)) = keys.iter().find(|(key, _)| key == name) else {
+                return Err(format!("unknown key {{{}}}, expected one of: {}",
+                    name, keys.iter().map(|(k, _)| *k).collect::<Vec<_>>().join(", ")));
+            };
+
+            match (spec, kind) {
+                (Spec::Default, _) => {}
+                (Spec::Precision(_), Kind::Bytes | Kind::Number) => {}
+                (Spec::Unit(..), Kind::Bytes) => {}
+                _ => return Err(format!("{{{}}} doesn't take this format", name)),
+            }
+        }
+
+        Ok(())
+    }
+
+    // plain - как красить текст вне тегов (обычно цветом темы)
+    pub fn render(&self, values: &[(&str, Value)], plain: &dyn Fn(&str) -> String) -> String {
+        let mut out = String::new();
+        let mut stack: Vec<&Style> = Vec::new();
+
+        for segment in &self.segments {
+            let text = match segment {
+                Segment::Open(style) => { stack.push(style); continue; }
+                Segment::Close       => { stack.pop(); continue; }
+                Segment::Literal(s)  => s.clone(),
+                Segment::Key { name, spec } => values.iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| format_value(value, spec))
+                    .unwrap_or_default(),
+            };
+
+            if stack.is_empty() {
+                out.push_str(&plain(&text));
+            } else {
+                out.push_str(&styled(&text, &stack));
+            }
+        }
+
+        out
+    }
+}
+
+fn styled(text: &str, stack: &[&Style]) -> String {
+    let color = stack.iter().rev().find_map(|s| match s {
+        Style::Color(c) => Some(c.as_str()),
+        _ => None,
+    });
+    let mut out = match color {
+        Some(color) => colorize(text, color),
+        None        => text.normal(),
+    };
+
+    for style in stack {
+        out = match style {
+            Style::Bold      => out.bold(),
+            Style::Dim       => out.dimmed(),
+            Style::Italic    => out.italic(),
+            Style::Underline => out.underline(),
+            Style::Color(_)  => out,
+        };
+    }
+    out.to_string()
+}
+
+// без единицы байты показываем в самой крупной подходящей двоичной
+fn human_bytes(bytes: u64, precision: usize) -> String {
+    let (name, div) = UNITS.iter()
+        .filter(|(name, _)| *name == "B" || name.ends_with("iB"))
+        .rev()
+        .find(|(_, div)| bytes as f64 >= *div)
+        .copied()
+        .unwrap_or(("B", 1.0));
+
+    match name {
+        "B" => format!("{} B", bytes),
+        _   => format!("{:.*} {}", precision, bytes as f64 / div, name),
+    }
+}
+
+fn format_value(value: &Value, spec: &Spec) -> String {
+    match (value, spec) {
+        (Value::Text(s), _) => s.clone(),
+        (Value::Number(n), Spec::Precision(p)) => format!("{:.*}", p, n),
+        (Value::Number(n), _) => format!("{:.0}", n),
+        (Value::Bytes(b), Spec::Default) => human_bytes(*b, 1),
+        (Value::Bytes(b), Spec::Precision(p)) => human_bytes(*b, *p),
+        (Value::Bytes(b), Spec::Unit(name, div, p)) => {
+            let p = p.unwrap_or(if *name == "B" { 0 } else { 1 });
+            format!("{:.*} {}", p, *b as f64 / div, name)
+        }
+    }
+}
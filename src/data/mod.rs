@@ -1,12 +1,21 @@
 pub mod collectors;
+pub mod format;
+pub mod custom;
+pub mod cache;
 
-use crate::config::InfoField;
-use crate::utils::shell_name;
+use crate::config::{Config, FieldRef, InfoField};
+use crate::utils::{parse_duration, shell_name};
+use cache::{field_key, Cache, Lookup};
+use custom::CustomValue;
 use collectors::*;
 
+use std::collections::HashMap;
 use std::env;
 use std::thread;
 
+const MIB: u64 = 1024 * 1024;
+const GIB: u64 = 1024 * MIB;
+
 pub struct SysData {
     pub os:                 Option<String>,
     pub kernel:             Option<String>,
@@ -14,21 +23,44 @@ pub struct SysData {
     pub host:               Option<String>,
     pub cpu:                Option<String>,
     pub gpu:                Option<String>,
-    pub memory_used_mb:     u64,
-    pub memory_total_mb:    u64,
-    pub swap_used_mb:       u64,
-    pub swap_total_mb:      u64,
-    pub disk_used_gb:       u64,
-    pub disk_total_gb:      u64,
+    // всё в байтах
+    pub memory_used:        u64,
+    pub memory_total:       u64,
+    pub swap_used:          u64,
+    pub swap_total:         u64,
+    pub disk_used:          u64,
+    pub disk_total:         u64,
     pub uptime_secs:        u64,
     pub shell:              Option<String>,
     pub terminal:           Option<String>,
     pub de:                 Option<String>,
     pub local_ip:           Option<String>,
+    // вывод кастомных полей, по порядку custom_fields
+    pub custom:             Vec<CustomValue>,
+    // встроенные поля с cache_ttl, важнее посчитанных
+    pub cached:             HashMap<InfoField, String>,
 }
 
 impl SysData {
-    pub fn collect() -> Self {
+    pub fn collect(config: &Config) -> Self {
+        Self::collect_with(config, false)
+    }
+
+    // --refresh-cache: всё с cache_ttl считаем заново и пишем в кэш, кэш не читаем
+    pub fn refresh_cache(config: &Config) {
+        Self::collect_with(config, true);
+        if let Some(cache) = Cache::open() { cache.unlock(); }
+    }
+
+    fn collect_with(config: &Config, refresh: bool) -> Self {
+        let uses_cache = !config.cache.ttl.is_empty()
+            || config.custom_fields.iter().any(|f| f.cache_ttl.is_some());
+        let cache = if uses_cache { Cache::open() } else { None };
+        let mut stale = false;
+
+        // кастомные команды самые медленные - запускаем первыми
+        let custom_pending  = custom::spawn_custom_fields(config, cache.as_ref(), refresh, &mut stale);
+
         // Запускаем тяжёлые операции параллельно
         let memory_handle   = thread::spawn(read_meminfo);
         let disk_handle     = thread::spawn(read_disk_root);
@@ -45,29 +77,74 @@ impl SysData {
         let local_ip    = read_local_ip();
 
         // получаем результаты из параллльных потоков
-        let (memory_used_mb, memory_total_mb, 
-             swap_used_mb, swap_total_mb) = memory_handle.join().unwrap();
-        let (disk_used_gb, disk_total_gb) = disk_handle.join().unwrap();
+        let (memory_used, memory_total,
+             swap_used,   swap_total) = memory_handle.join().unwrap();
+        let (disk_used, disk_total) = disk_handle.join().unwrap();
 
         let uptime_secs = uptime_handle.join().unwrap();
+        let custom = custom::finish_custom_fields(config, custom_pending, cache.as_ref());
 
-        Self {
+        let mut data = Self {
             uptime_secs, terminal, local_ip,
             kernel,      shell,    cpu,
             arch,        host,     os,
 
-            memory_used_mb, memory_total_mb,
-            swap_used_mb,   swap_total_mb,
-            disk_used_gb,   disk_total_gb,
+            memory_used, memory_total,
+            swap_used,   swap_total,
+            disk_used,   disk_total,
 
             gpu: None,
+            custom,
+            cached: HashMap::new(),
 
             de: env::var("XDG_CURRENT_DESKTOP")
                 .or_else(|_| env::var("DESKTOP_SESSION")).ok(),
+        };
+
+        if let Some(cache) = &cache {
+            data.cached = data.cache_fields(config, cache, refresh, &mut stale);
         }
+        // протухшее уже в выводе, свежее посчитает фоновый процесс
+        if stale && !refresh && let Some(cache) = &cache {
+            cache.refresh_in_background();
+        }
+
+        data
+    }
+
+    fn cache_fields(&self, config: &Config, cache: &Cache, refresh: bool, stale: &mut bool) -> HashMap<InfoField, String> {
+        let mut cached = HashMap::new();
+        // только то, что выводится: public_ip в выключенной категории в сеть не ходит
+        let shown = |field| config.categories.iter()
+            .filter(|c| c.enabled)
+            .any(|c| c.field_list().contains(&FieldRef::Builtin(field)));
+
+        for (&field, ttl) in &config.cache.ttl {
+            if !shown(field) { continue; }
+            let Some(ttl) = parse_duration(ttl) else { continue };
+            let key = field_key(field);
+
+            let value = match cache.get(&key, ttl) {
+                Lookup::Fresh(value) if !refresh => Some(value),
+                Lookup::Stale(value) if !refresh && config.cache.background => {
+                    *stale = true;
+                    Some(value)
+                }
+                _ => {
+                    let value = self.get(field);
+                    if let Some(value) = &value { cache.put(&key, value); }
+                    value
+                }
+            };
+            if let Some(value) = value { cached.insert(field, value); }
+        }
+
+        cached
     }
 
     pub fn get(&self, field: InfoField) -> Option<String> {
+        if let Some(value) = self.cached.get(&field) { return Some(value.clone()); }
+
         match field {
             InfoField::Os       => self.os.as_ref().cloned(),
             InfoField::Kernel   => self.kernel.as_ref().cloned(),
@@ -77,21 +154,21 @@ impl SysData {
             InfoField::Gpu      => detect_gpu(),
             InfoField::Memory   => Some(format!(
                 "{} MB / {} MB",
-                self.memory_used_mb, self.memory_total_mb
+                self.memory_used / MIB, self.memory_total / MIB
             )),
             InfoField::Swap => {
-                if self.swap_total_mb == 0 {
+                if self.swap_total == 0 {
                     Some("N/A".to_string())
                 } else {
                     Some(format!(
                         "{} MB / {} MB",
-                        self.swap_used_mb, self.swap_total_mb
+                        self.swap_used / MIB, self.swap_total / MIB
                     ))
                 }
             }
             InfoField::Disk => Some(format!(
                 "{} GB / {} GB",
-                self.disk_used_gb, self.disk_total_gb
+                self.disk_used / GIB, self.disk_total / GIB
             )),
             InfoField::Uptime   => Some(format_uptime(self.uptime_secs)),
             InfoField::Shell    => self.shell.as_ref().cloned(),
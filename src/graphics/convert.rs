@@ -0,0 +1,116 @@
+use crate::ascii::Fit;
+use crate::config::{ImageAsciiStyle, LogoConfig};
+use crate::graphics::{cell_size, image_cells, load_image};
+use crate::ui::colors::{colorize_rgb, on_rgb, Rgb};
+use image::{imageops::FilterType, Rgba, RgbaImage};
+
+const DEFAULT_RAMP: &str = " .:-=+*#%@";
+
+// сколько пикселей картинки уходит в одну ячейку
+fn pixels_per_cell(style: ImageAsciiStyle) -> (u32, u32) {
+    match style {
+        ImageAsciiStyle::HalfBlock => (1, 2),
+        ImageAsciiStyle::Braille   => (2, 4),
+        ImageAsciiStyle::Ramp      => (1, 1),
+    }
+}
+
+fn opaque(px: &Rgba<u8>) -> bool {
+    px[3] >= 128
+}
+
+fn rgb(px: &Rgba<u8>) -> Rgb {
+    (px[0], px[1], px[2])
+}
+
+fn luma(px: &Rgba<u8>) -> f32 {
+    (0.299 * px[0] as f32 + 0.587 * px[1] as f32 + 0.114 * px[2] as f32) / 255.0
+}
+
+fn average(pixels: &[&Rgba<u8>]) -> Rgb {
+    let n = pixels.len().max(1) as u32;
+    let sum = pixels.iter().fold((0, 0, 0), |(r, g, b), px| {
+        (r + px[0] as u32, g + px[1] as u32, b + px[2] as u32)
+    });
+    ((sum.0 / n) as u8, (sum.1 / n) as u8, (sum.2 / n) as u8)
+}
+
+// ▀ - верхний пиксель цветом символа, нижний фоном. прозрачное не красим
+fn half_block(img: &RgbaImage, x: u32, y: u32) -> String {
+    let top = img.get_pixel(x, y * 2);
+    let bottom = img.get_pixel(x, y * 2 + 1);
+
+    match (opaque(top), opaque(bottom)) {
+        (false, false) => " ".to_string(),
+        (true, false)  => colorize_rgb("▀", rgb(top)).to_string(),
+        (false, true)  => colorize_rgb("▄", rgb(bottom)).to_string(),
+        (true, true)   => on_rgb(colorize_rgb("▀", rgb(top)), rgb(bottom)).to_string(),
+    }
+}
+
+// точки брайля 2x4, биты идут не по порядку
+const BRAILLE_BITS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+fn braille(img: &RgbaImage, x: u32, y: u32, lit: &dyn Fn(&Rgba<u8>) -> bool) -> String {
+    let mut bits = 0;
+    let mut on: Vec<&Rgba<u8>> = vec![];
+
+    for (dy, row) in BRAILLE_BITS.iter().enumerate() {
+        for (dx, bit) in row.iter().enumerate() {
+            let px = img.get_pixel(x * 2 + dx as u32, y * 4 + dy as u32);
+            if lit(px) {
+                bits |= bit;
+                on.push(px);
+            }
+        }
+    }
+
+    if bits == 0 { return " ".to_string(); }
+    let ch = char::from_u32(0x2800 + bits).unwrap_or(' ');
+    colorize_rgb(ch.encode_utf8(&mut [0; 4]), average(&on)).to_string()
+}
+
+fn ramp(img: &RgbaImage, x: u32, y: u32, chars: &[char]) -> String {
+    let px = img.get_pixel(x, y);
+    if !opaque(px) || chars.is_empty() { return " ".to_string(); }
+
+    let idx = ((luma(px) * (chars.len() - 1) as f32).round() as usize).min(chars.len() - 1);
+    match chars[idx] {
+        ' ' => " ".to_string(),
+        ch  => colorize_rgb(ch.encode_utf8(&mut [0; 4]), rgb(px)).to_string(),
+    }
+}
+
+// строки уже раскрашены escape-последовательностями, красить их второй раз не надо
+pub fn image_to_ascii(cfg: &LogoConfig, default_rows: usize, fit: &Fit) -> Result<Vec<String>, String> {
+    let path = cfg.image.as_deref()
+        .ok_or_else(|| "logo.type = \"image-ascii\" needs logo.image".to_string())?;
+    let img = load_image(path)?;
+
+    let (mut cols, mut rows) = image_cells(cfg, (img.width(), img.height()), cell_size(), default_rows);
+    // размер не задан явно - ужимаем под свободное место
+    if cfg.width.is_none() && let Some(max) = fit.max_width && cols > max && max > 0 {
+        rows = (rows * max / cols).max(1);
+        cols = max;
+    }
+
+    let (px_w, px_h) = pixels_per_cell(cfg.style);
+    let img = img.resize_exact(cols as u32 * px_w, rows as u32 * px_h, FilterType::Triangle).to_rgba8();
+
+    // у картинки с прозрачным фоном точки - всё непрозрачное, иначе - всё светлее среднего
+    let transparent = img.pixels().any(|px| !opaque(px));
+    let mean = img.pixels().map(luma).sum::<f32>() / img.pixels().len().max(1) as f32;
+    let lit = move |px: &Rgba<u8>| opaque(px) && (transparent || luma(px) >= mean);
+
+    let chars: Vec<char> = cfg.ramp.as_deref().unwrap_or(DEFAULT_RAMP).chars().collect();
+
+    let lines = (0..rows as u32).map(|y| {
+        (0..cols as u32).map(|x| match cfg.style {
+            ImageAsciiStyle::HalfBlock => half_block(&img, x, y),
+            ImageAsciiStyle::Braille   => braille(&img, x, y, &lit),
+            ImageAsciiStyle::Ramp      => ramp(&img, x, y, &chars),
+        }).collect()
+    }).collect();
+
+    Ok(lines)
+}
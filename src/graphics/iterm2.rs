@@ -0,0 +1,22 @@
+use crate::graphics::base64;
+
+// OSC 1337 File=..., размер в ячейках терминала
+pub fn encode(data: &[u8], cols: usize, rows: usize) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+        data.len(), cols, rows, base64(data)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_file_in_osc_1337() {
+        assert_eq!(
+            encode(b"hi", 10, 5),
+            "\x1b]1337;File=inline=1;size=2;width=10;height=5;preserveAspectRatio=1:aGk=\x07",
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::graphics::base64;
+
+// kitty не принимает больше 4096 байт base64 за раз
+const CHUNK: usize = 4096;
+
+// a=T - передать и показать, f=100 - png, C=1 - не двигать курсор, q=2 - без ответов
+pub fn encode(png: &[u8], cols: usize, rows: usize) -> String {
+    let data = base64(png);
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(CHUNK).collect();
+    let mut out = String::new();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 < chunks.len() { 1 } else { 0 };
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=100,c={},r={},C=1,q=2,m={};{}\x1b\\",
+                cols, rows, more, chunk
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ESC _ G ... ESC \ на каждый кусок
+    fn commands(out: &str) -> Vec<&str> {
+        out.split_terminator("\x1b\\")
+            .map(|cmd| cmd.strip_prefix("\x1b_G").expect("APC G"))
+            .collect()
+    }
+
+    #[test]
+    fn small_image_is_one_final_chunk() {
+        let out = encode(b"png", 10, 5);
+        assert_eq!(out, format!("\x1b_Ga=T,f=100,c=10,r=5,C=1,q=2,m=0;{}\x1b\\", base64(b"png")));
+    }
+
+    #[test]
+    fn large_image_is_split_into_4096_byte_chunks() {
+        let png = vec![0xabu8; 7000];
+        let data = base64(&png);
+        let out = encode(&png, 20, 10);
+        let cmds = commands(&out);
+        assert_eq!(cmds.len(), data.len().div_ceil(CHUNK));
+
+        let mut payload = String::new();
+        for (i, cmd) in cmds.iter().enumerate() {
+            let (keys, chunk) = cmd.split_once(';').unwrap();
+            let last = i + 1 == cmds.len();
+            match i {
+                0 => assert_eq!(keys, "a=T,f=100,c=20,r=10,C=1,q=2,m=1"),
+                _ => assert_eq!(keys, if last { "m=0" } else { "m=1" }),
+            }
+            if !last { assert_eq!(chunk.len(), CHUNK); }
+            payload.push_str(chunk);
+        }
+        assert_eq!(payload, data);
+    }
+}
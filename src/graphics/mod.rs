@@ -0,0 +1,159 @@
+pub mod kitty;
+pub mod iterm2;
+pub mod sixel;
+pub mod convert;
+
+use crate::config::{ImageProtocol, LogoConfig};
+use crate::utils::{expand_home, terminal_size};
+use image::{DynamicImage, ImageFormat, imageops::FilterType};
+use std::env;
+use std::io::Cursor;
+
+// если терминал не сказал размер ячейки в пикселях - берём типичный
+const DEFAULT_CELL: (usize, usize) = (10, 20);
+const MAX_DEFAULT_ROWS: usize = 20;
+
+// готовая картинка: escape последовательность и сколько ячеек она занимает
+pub struct ImageLogo {
+    pub escape: String,
+    pub cols:   usize,
+    pub rows:   usize,
+}
+
+fn is_tty() -> bool {
+    unsafe { libc::isatty(libc::STDOUT_FILENO) == 1 }
+}
+
+// только по окружению, без запросов к терминалу. в tmux картинки не пролезают
+pub fn detect_protocol() -> Option<ImageProtocol> {
+    let var  = |name: &str| env::var(name).unwrap_or_default();
+    let term = var("TERM");
+    let program = var("TERM_PROGRAM");
+
+    if !is_tty() || env::var("TMUX").is_ok() || term.starts_with("screen") {
+        return None;
+    }
+
+    if env::var("KITTY_WINDOW_ID").is_ok()
+        || term == "xterm-kitty" || term == "xterm-ghostty"
+        || program == "WezTerm"  || program == "ghostty" {
+        Some(ImageProtocol::Kitty)
+    } else if program == "iTerm.app" || program == "mintty" || env::var("LC_TERMINAL").is_ok_and(|t| t == "iTerm2") {
+        Some(ImageProtocol::Iterm2)
+    } else if term.contains("sixel") || term == "foot" || term.starts_with("foot-")
+        || term == "mlterm" || term == "yaft-256color" || program == "contour" {
+        Some(ImageProtocol::Sixel)
+    } else {
+        None
+    }
+}
+
+pub fn cell_size() -> (usize, usize) {
+    match terminal_size() {
+        Some(t) if t.width_px > 0 && t.height_px > 0 && t.rows > 0 =>
+            (t.width_px / t.cols, t.height_px / t.rows),
+        _ => DEFAULT_CELL,
+    }
+}
+
+// размер в ячейках: заданное в конфиге, недостающее - по пропорциям картинки
+pub fn image_cells(cfg: &LogoConfig, (img_w, img_h): (u32, u32), (cell_w, cell_h): (usize, usize), default_rows: usize) -> (usize, usize) {
+    // сколько колонок приходится на одну строку при сохранении пропорций
+    let aspect = (img_w as f32 / cell_w as f32) / (img_h.max(1) as f32 / cell_h as f32);
+
+    match (cfg.width, cfg.height) {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None)    => (w, ((w as f32 / aspect).round() as usize).max(1)),
+        (None, Some(h))    => (((h as f32 * aspect).round() as usize).max(1), h),
+        (None, None)       => {
+            let h = default_rows.clamp(1, MAX_DEFAULT_ROWS);
+            (((h as f32 * aspect).round() as usize).max(1), h)
+        }
+    }
+}
+
+pub fn load_image(path: &str) -> Result<DynamicImage, String> {
+    let path = expand_home(path);
+    image::open(&path)
+        .map_err(|e| format!("can't load image {}: {}", path.display(), e))
+}
+
+// картинка под размер ячеек, чтобы не гонять в терминал лишние мегабайты
+pub fn fit_image(img: &DynamicImage, (cols, rows): (usize, usize), (cell_w, cell_h): (usize, usize)) -> DynamicImage {
+    img.resize((cols * cell_w) as u32, (rows * cell_h) as u32, FilterType::Triangle)
+}
+
+pub fn encode_png(img: &DynamicImage) -> Result<Vec<u8>, String> {
+    let mut buf = Cursor::new(Vec::new());
+    img.write_to(&mut buf, ImageFormat::Png)
+        .map_err(|e| format!("can't encode image: {}", e))?;
+    Ok(buf.into_inner())
+}
+
+// Ok(None) - терминал картинки не умеет, рисуем ascii
+pub fn load_image_logo(cfg: &LogoConfig, default_rows: usize) -> Result<Option<ImageLogo>, String> {
+    let protocol = match cfg.protocol {
+        ImageProtocol::Auto => match detect_protocol() {
+            Some(p) => p,
+            None    => return Ok(None),
+        },
+        p => p,
+    };
+
+    let path = cfg.image.as_deref()
+        .ok_or_else(|| "logo.type = \"image\" needs logo.image".to_string())?;
+    let img = load_image(path)?;
+
+    let cell = cell_size();
+    let (cols, rows) = image_cells(cfg, (img.width(), img.height()), cell, default_rows);
+    let img = fit_image(&img, (cols, rows), cell);
+
+    let escape = match protocol {
+        ImageProtocol::Kitty  => kitty::encode(&encode_png(&img)?, cols, rows),
+        ImageProtocol::Iterm2 => iterm2::encode(&encode_png(&img)?, cols, rows),
+        ImageProtocol::Sixel  => sixel::encode(&img.to_rgba8()),
+        ImageProtocol::Auto   => unreachable!(),
+    };
+
+    Ok(Some(ImageLogo { escape, cols, rows }))
+}
+
+// оба протокола хотят base64, тащить ради этого крейт не стоит
+pub fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4648, раздел 10
+    #[test]
+    fn base64_matches_rfc_4648_vectors() {
+        let vectors = [
+            ("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="), ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy"),
+        ];
+        for (input, expected) in vectors {
+            assert_eq!(base64(input.as_bytes()), expected, "base64({:?})", input);
+        }
+    }
+
+    #[test]
+    fn base64_uses_the_whole_alphabet() {
+        assert_eq!(base64(&[0xfb, 0xff, 0xbf]), "+/+/");
+        assert_eq!(base64(&[0x00, 0x10, 0x83]), "ABCD");
+    }
+}
@@ -0,0 +1,101 @@
+use image::RgbaImage;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+// куб 6x6x6, для лого хватает с головой
+const LEVELS: u32 = 6;
+
+fn level(c: u8) -> u32 {
+    (c as u32 * (LEVELS - 1) + 127) / 255
+}
+
+// 0 - прозрачный, палитра начинается с 1
+fn color_index(px: &image::Rgba<u8>) -> usize {
+    if px[3] < 128 { return 0; }
+    (1 + level(px[0]) * LEVELS * LEVELS + level(px[1]) * LEVELS + level(px[2])) as usize
+}
+
+fn push_run(out: &mut String, ch: char, count: usize) {
+    match count {
+        0 => {}
+        1..=3 => (0..count).for_each(|_| out.push(ch)),
+        _ => { let _ = write!(out, "!{}{}", count, ch); }
+    }
+}
+
+// DCS q ... ST, по 6 строк пикселей за полосу, фон остаётся прозрачным (P2 = 1)
+pub fn encode(img: &RgbaImage) -> String {
+    let (w, h) = img.dimensions();
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", w, h);
+
+    let used: BTreeSet<usize> = img.pixels().map(color_index).filter(|&i| i > 0).collect();
+    for &i in &used {
+        let c = (i - 1) as u32;
+        let pct = |l: u32| l * 100 / (LEVELS - 1);
+        let _ = write!(out, "#{};2;{};{};{}",
+            i, pct(c / (LEVELS * LEVELS)), pct(c / LEVELS % LEVELS), pct(c % LEVELS));
+    }
+
+    for band in (0..h).step_by(6) {
+        let rows = band..(band + 6).min(h);
+        let colors: BTreeSet<usize> = rows.clone()
+            .flat_map(|y| (0..w).map(move |x| (x, y)))
+            .map(|(x, y)| color_index(img.get_pixel(x, y)))
+            .filter(|&i| i > 0)
+            .collect();
+
+        for &color in &colors {
+            let _ = write!(out, "#{}", color);
+
+            let (mut prev, mut count) = ('?', 0);
+            for x in 0..w {
+                let bits = rows.clone()
+                    .filter(|&y| color_index(img.get_pixel(x, y)) == color)
+                    .fold(0u8, |acc, y| acc | 1 << (y - band));
+                let ch = (63 + bits) as char;
+
+                if ch == prev {
+                    count += 1;
+                } else {
+                    push_run(&mut out, prev, count);
+                    (prev, count) = (ch, 1);
+                }
+            }
+            push_run(&mut out, prev, count);
+            out.push('$');
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn transparent_image_is_header_and_terminator() {
+        let img = RgbaImage::from_pixel(1, 1, Rgba([0, 0, 0, 0]));
+        assert_eq!(encode(&img), "\x1bP0;1;0q\"1;1;1;1-\x1b\\");
+    }
+
+    #[test]
+    fn red_row_defines_color_and_paints_top_pixels() {
+        // красный: 1 + 5*36 = 181, верхний пиксель полосы - бит 0, '?' + 1 = '@'
+        let img = RgbaImage::from_pixel(2, 1, Rgba([255, 0, 0, 255]));
+        assert_eq!(encode(&img), "\x1bP0;1;0q\"1;1;2;1#181;2;100;0;0#181@@$-\x1b\\");
+    }
+
+    #[test]
+    fn long_runs_are_compressed() {
+        let img = RgbaImage::from_pixel(5, 7, Rgba([255, 0, 0, 255]));
+        let out = encode(&img);
+        assert!(out.starts_with("\x1bP0;1;0q\"1;1;5;7"));
+        // 6 строк - полоса '~', седьмая - вторая полоса с одним битом
+        assert!(out.contains("#181!5~$-#181!5@$-"));
+        assert!(out.ends_with("\x1b\\"));
+    }
+}
@@ -1,6 +1,8 @@
+pub mod cli;
 pub mod config;
 pub mod data;
 pub mod ascii;
+pub mod graphics;
 pub mod ui;
 pub mod utils;
 pub mod localization;
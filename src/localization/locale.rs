@@ -0,0 +1,221 @@
+use crate::config::check::{did_you_mean, names_of, suggest};
+use crate::config::{BuiltinCategory, InfoField, Language};
+use crate::utils::config_home;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+// встроенные переводы, те же файлы что лежат в locales/ репозитория
+const BUILTIN_LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../../locales/en.toml")),
+    ("ru", include_str!("../../locales/ru.toml")),
+    ("de", include_str!("../../locales/de.toml")),
+    ("es", include_str!("../../locales/es.toml")),
+    ("fr", include_str!("../../locales/fr.toml")),
+    ("uk", include_str!("../../locales/uk.toml")),
+    ("zh", include_str!("../../locales/zh.toml")),
+    ("ja", include_str!("../../locales/ja.toml")),
+];
+
+const SYSTEM_LOCALES_DIR: &str = "/usr/share/rushfetch/locales";
+
+// старые конфиги писали язык словом
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("english",   "en"),
+    ("russian",   "ru"),
+    ("german",    "de"),
+    ("spanish",   "es"),
+    ("french",    "fr"),
+    ("ukrainian", "uk"),
+    ("chinese",   "zh"),
+    ("japanese",  "ja"),
+];
+
+// как у gettext: первая непустая
+const LOCALE_ENV: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+pub fn locale_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = config_home() {
+        dirs.push(home.join("rushfetch/locales"));
+    }
+    dirs.push(PathBuf::from(SYSTEM_LOCALES_DIR));
+    dirs
+}
+
+pub fn builtin_locale_names() -> impl Iterator<Item = &'static str> {
+    BUILTIN_LOCALES.iter().map(|(code, _)| *code)
+}
+
+// "de_DE.UTF-8" -> ["de_de", "de"]: сначала точный перевод, потом просто язык.
+// регистр тут не важен, файл ищет locale_file
+fn candidates(value: &str) -> Vec<String> {
+    let value = value.trim().to_lowercase();
+    if let Some((_, code)) = LANGUAGE_NAMES.iter().find(|(name, _)| *name == value) {
+        return vec![code.to_string()];
+    }
+
+    let tag = value.split(['.', '@']).next().unwrap_or_default().replace('-', "_");
+    if tag == "c" || tag == "posix" { return vec!["en".to_string()]; }
+
+    let mut codes = vec![tag.clone()];
+    if let Some((lang, _)) = tag.split_once('_') { codes.push(lang.to_string()); }
+    codes
+}
+
+// pt_BR.toml найдётся и по "pt_br", и по "pt-BR"
+fn locale_file(dir: &Path, code: &str) -> Option<PathBuf> {
+    fs::read_dir(dir).ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .find(|path| path.is_file()
+            && path.extension().is_some_and(|ext| ext == "toml")
+            && path.file_stem().and_then(|stem| stem.to_str()).is_some_and(|stem| stem.eq_ignore_ascii_case(code)))
+}
+
+fn exists(code: &str) -> bool {
+    builtin_locale_names().any(|c| c == code)
+        || locale_dirs().iter().any(|dir| locale_file(dir, code).is_some())
+}
+
+// код перевода; None - такого языка нет ни встроенного, ни в файлах.
+// "auto" без подходящего перевода - просто английский
+pub fn resolve(language: &Language) -> Option<String> {
+    if !language.0.trim().eq_ignore_ascii_case("auto") {
+        return candidates(&language.0).into_iter().find(|code| exists(code));
+    }
+
+    let value = LOCALE_ENV.iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty());
+    let code = value.and_then(|value| candidates(&value).into_iter().find(|code| exists(code)));
+    Some(code.unwrap_or_else(|| "en".to_string()))
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct LocaleFile {
+    #[serde(default)] categories: Table,
+    #[serde(default)] fields:     Table,
+}
+
+// названия категорий и полей по id из конфига: "resources", "local_ip"
+#[derive(Debug, Clone, Default)]
+pub struct Locale {
+    categories: HashMap<String, String>,
+    fields:     HashMap<String, String>,
+}
+
+impl Locale {
+    // английский -> встроенный перевод -> /usr/share -> ~/.config, каждый следующий перекрывает.
+    // чего нет в переводе, остаётся по-английски. ошибки отдаём, а не печатаем - см. check_language
+    pub fn load(language: &Language) -> (Self, Vec<String>) {
+        let mut locale = Self::default();
+        let mut problems = Vec::new();
+
+        let code = resolve(language).unwrap_or_else(|| {
+            problems.push(format!(
+                "unknown language \"{}\", expected \"auto\", one of {} or a file in ~/.config/rushfetch/locales/; using English",
+                language.0, builtin_locale_names().collect::<Vec<_>>().join(" "),
+            ));
+            "en".to_string()
+        });
+
+        let mut sources: Vec<(String, String)> = BUILTIN_LOCALES.iter()
+            .filter(|(c, _)| *c == "en" || *c == code)
+            .map(|(c, text)| (format!("built-in {}", c), text.to_string()))
+            .collect();
+        for dir in locale_dirs().iter().rev() {
+            let Some(path) = locale_file(dir, &code) else { continue };
+            match fs::read_to_string(&path) {
+                Ok(text) => sources.push((path.display().to_string(), text)),
+                Err(e)   => problems.push(format!("can't read {}: {}", path.display(), e)),
+            }
+        }
+
+        for (origin, text) in sources {
+            if let Err(e) = locale.merge(&origin, &text, &mut problems) {
+                problems.push(format!("{}: broken translation: {}", origin, e));
+            }
+        }
+        (locale, problems)
+    }
+
+    fn merge(&mut self, origin: &str, text: &str, problems: &mut Vec<String>) -> Result<(), String> {
+        let file: LocaleFile = toml::from_str(text).map_err(|e| e.message().to_string())?;
+
+        for (key, value) in file.categories {
+            let Some(value) = value.as_str() else {
+                problems.push(format!("{}: categories.{} must be a string", origin, key));
+                continue;
+            };
+            // "res" и прочие старые имена тоже понимаем
+            let id = match BuiltinCategory::deserialize(Value::from(key.as_str())) {
+                Ok(category)              => category.name(),
+                Err(_) if key == "custom" => key,
+                Err(_) => {
+                    let known = names_of::<BuiltinCategory>().iter().copied().chain(["custom"]);
+                    problems.push(format!("{}: unknown category \"{}\"{}", origin, key, did_you_mean(suggest(&key, known))));
+                    continue;
+                }
+            };
+            self.categories.insert(id, value.to_string());
+        }
+
+        for (key, value) in file.fields {
+            let Some(value) = value.as_str() else {
+                problems.push(format!("{}: fields.{} must be a string", origin, key));
+                continue;
+            };
+            match InfoField::deserialize(Value::from(key.as_str())) {
+                Ok(field) => { self.fields.insert(field.name(), value.to_string()); }
+                Err(_) => problems.push(format!("{}: unknown field \"{}\"{}", origin, key,
+                    did_you_mean(suggest(&key, names_of::<InfoField>().iter().copied())))),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn category(&self, category: BuiltinCategory) -> &str {
+        let id = category.name();
+        self.categories.get(&id).map(String::as_str).unwrap_or("")
+    }
+
+    // заголовок для custom_fields, не попавших ни в одну категорию
+    pub fn custom(&self) -> &str {
+        self.categories.get("custom").map(String::as_str).unwrap_or("Custom")
+    }
+
+    pub fn field(&self, field: InfoField) -> &str {
+        self.fields.get(&field.name()).map(String::as_str).unwrap_or("")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_go_from_region_to_language() {
+        assert_eq!(candidates("de_DE.UTF-8"), ["de_de", "de"]);
+        assert_eq!(candidates("pt-BR"), ["pt_br", "pt"]);
+        assert_eq!(candidates("Russian"), ["ru"]);
+        assert_eq!(candidates("C.UTF-8"), ["en"]);
+    }
+
+    #[test]
+    fn locale_file_ignores_case() {
+        let dir = env::temp_dir().join(format!("rushfetch-locales-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("pt_BR.toml"), "").unwrap();
+
+        let found = locale_file(&dir, "pt_br");
+        let missing = locale_file(&dir, "pt");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(found, Some(dir.join("pt_BR.toml")));
+        assert_eq!(missing, None);
+    }
+}
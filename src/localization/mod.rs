@@ -1,3 +1,5 @@
 pub mod categories;
+pub mod locale;
 
-pub use categories::{localize_category, localize_field, category_icon};
+pub use categories::category_icon;
+pub use locale::Locale;
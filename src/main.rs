@@ -1,8 +1,35 @@
-use rushfetch::{SysData, Renderer, config::load_config};
+use rushfetch::{SysData, Renderer, cli::Args};
+use rushfetch::config::{check_config, explain_config, load_config};
+use rushfetch::config::generate::{gen_config, render_config};
+use rushfetch::config::migrate::migrate_config;
 
 fn main() {
-    let config   = load_config();
-    let data     = SysData::collect();
+    let args     = Args::parse();
+    if args.explain_config {
+        explain_config(&args);
+        return;
+    }
+    if args.migrate_config {
+        std::process::exit(if migrate_config(&args) { 0 } else { 1 });
+    }
+    if args.gen_config {
+        std::process::exit(if gen_config(&args) { 0 } else { 1 });
+    }
+    if args.check_config {
+        std::process::exit(if check_config(&args) { 0 } else { 1 });
+    }
+
+    let config   = load_config(&args);
+    if args.print_config {
+        print!("{}", render_config(&config, false));
+        return;
+    }
+    if args.refresh_cache {
+        SysData::refresh_cache(&config);
+        return;
+    }
+
+    let data     = SysData::collect(&config);
     let renderer = Renderer::new(&config, &data);
 
     renderer.render();
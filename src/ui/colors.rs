@@ -1,6 +1,35 @@
 use colored::*;
+use std::env;
+use std::sync::OnceLock;
 
-pub fn colorize<'a>(text: &'a str, color: &str) -> ColoredString {
+pub type Rgb = (u8, u8, u8);
+
+// стандартная xterm палитра, нужна чтобы сводить truecolor к 16 цветам
+const ANSI_COLORS: &[(&str, Rgb)] = &[
+    ("black",           (0,   0,   0  )),
+    ("red",             (205, 0,   0  )),
+    ("green",           (0,   205, 0  )),
+    ("yellow",          (205, 205, 0  )),
+    ("blue",            (0,   0,   238)),
+    ("magenta",         (205, 0,   205)),
+    ("cyan",            (0,   205, 205)),
+    ("white",           (229, 229, 229)),
+    ("bright_black",    (127, 127, 127)),
+    ("bright_red",      (255, 0,   0  )),
+    ("bright_green",    (0,   255, 0  )),
+    ("bright_yellow",   (255, 255, 0  )),
+    ("bright_blue",     (92,  92,  255)),
+    ("bright_magenta",  (255, 0,   255)),
+    ("bright_cyan",     (0,   255, 255)),
+    ("bright_white",    (255, 255, 255)),
+];
+
+// имя цвета по номеру в палитре терминала, 0..15
+pub fn ansi_name(idx: usize) -> Option<&'static str> {
+    ANSI_COLORS.get(idx).map(|(name, _)| *name)
+}
+
+pub fn colorize(text: &str, color: &str) -> ColoredString {
     match color {
         "black"             => text.black(),
         "red"               => text.red(),
@@ -18,6 +47,72 @@ pub fn colorize<'a>(text: &'a str, color: &str) -> ColoredString {
         "bright_magenta"    => text.bright_magenta(),
         "bright_cyan"       => text.bright_cyan(),
         "bright_white"      => text.bright_white(),
-        _ => text.white(),
+        _ => match parse_hex(color) {
+            Some(rgb) => colorize_rgb(text, rgb),
+            None      => text.white(),
+        },
+    }
+}
+
+// в 16-цветном терминале подбираем ближайший из палитры
+pub fn colorize_rgb(text: &str, (r, g, b): Rgb) -> ColoredString {
+    if supports_truecolor() {
+        text.truecolor(r, g, b)
+    } else {
+        colorize(text, nearest_ansi((r, g, b)))
+    }
+}
+
+// то же для фона, нужно полублокам
+pub fn on_rgb(text: ColoredString, (r, g, b): Rgb) -> ColoredString {
+    if supports_truecolor() {
+        text.on_truecolor(r, g, b)
+    } else {
+        text.on_color(Color::from(nearest_ansi((r, g, b)).replace('_', " ")))
+    }
+}
+
+pub fn supports_truecolor() -> bool {
+    static TRUECOLOR: OnceLock<bool> = OnceLock::new();
+
+    *TRUECOLOR.get_or_init(|| {
+        env::var("COLORTERM")
+            .map(|v| v == "truecolor" || v == "24bit")
+            .unwrap_or(false)
+    })
+}
+
+// "#rrggbb" или "#rgb"
+pub fn parse_hex(color: &str) -> Option<Rgb> {
+    let hex = color.strip_prefix('#')?;
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+
+    match hex.len() {
+        6 => Some((channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?)),
+        3 => {
+            let (r, g, b) = (channel(&hex[0..1])?, channel(&hex[1..2])?, channel(&hex[2..3])?);
+            Some((r * 17, g * 17, b * 17))
+        }
+        _ => None,
     }
 }
+
+// и именованные цвета, и hex
+pub fn parse_rgb(color: &str) -> Option<Rgb> {
+    ANSI_COLORS.iter()
+        .find(|(name, _)| *name == color)
+        .map(|(_, rgb)| *rgb)
+        .or_else(|| parse_hex(color))
+}
+
+pub fn nearest_ansi((r, g, b): Rgb) -> &'static str {
+    let dist = |(cr, cg, cb): Rgb| {
+        let (dr, dg, db) = (r as i32 - cr as i32, g as i32 - cg as i32, b as i32 - cb as i32);
+        dr * dr + dg * dg + db * db
+    };
+
+    ANSI_COLORS.iter()
+        .min_by_key(|(_, rgb)| dist(*rgb))
+        .map(|(name, _)| *name)
+        .unwrap_or("white")
+}
@@ -0,0 +1,161 @@
+use crate::config::{BorderStyle, BoxScope, LayoutConfig};
+use crate::ui::colors::colorize;
+use crate::ui::layout::{fit_info, InfoLine, LineKind};
+use crate::utils::{ellipsize, pad_to_width, unicode_str_width};
+use std::env;
+
+struct Border {
+    top_left:     &'static str,
+    top_right:    &'static str,
+    bottom_left:  &'static str,
+    bottom_right: &'static str,
+    horizontal:   &'static str,
+    vertical:     &'static str,
+}
+
+const ROUNDED: Border = Border {
+    top_left: "╭", top_right: "╮", bottom_left: "╰", bottom_right: "╯", horizontal: "─", vertical: "│",
+};
+const SQUARE: Border = Border {
+    top_left: "┌", top_right: "┐", bottom_left: "└", bottom_right: "┘", horizontal: "─", vertical: "│",
+};
+const ASCII: Border = Border {
+    top_left: "+", top_right: "+", bottom_left: "+", bottom_right: "+", horizontal: "-", vertical: "|",
+};
+
+// рамка слева и справа: "│ " и " │"
+pub const FRAME_WIDTH: usize = 4;
+
+// локаль явно не utf-8 (C, POSIX, koi8-r...) - псевдографику не рисуем
+fn utf8_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"].iter()
+        .find_map(|var| env::var(var).ok().filter(|v| !v.is_empty()))
+        .is_none_or(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+fn border(style: BorderStyle) -> &'static Border {
+    match style {
+        _ if !utf8_locale()  => &ASCII,
+        BorderStyle::Rounded => &ROUNDED,
+        BorderStyle::Square  => &SQUARE,
+        BorderStyle::Ascii   => &ASCII,
+    }
+}
+
+// кусок инфы: строка как есть или рамка с заголовком
+enum Piece<'a> {
+    Plain(&'a InfoLine),
+    Boxed { title: Option<&'a InfoLine>, content: Vec<InfoLine> },
+}
+
+// строки, которые попадают в рамку - без пустых по краям
+fn trim_blank(mut content: Vec<InfoLine>) -> Vec<InfoLine> {
+    while content.last().is_some_and(|l| l.kind == LineKind::Blank) { content.pop(); }
+    while content.first().is_some_and(|l| l.kind == LineKind::Blank) { content.remove(0); }
+    content
+}
+
+fn split(lines: &[InfoLine], scope: BoxScope) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+
+    match scope {
+        // заголовком рамки становится user@host, разделитель под ним уже не нужен
+        BoxScope::Info => {
+            let title = lines.iter().find(|l| l.kind == LineKind::Header);
+            let content = lines.iter()
+                .filter(|l| !matches!(l.kind, LineKind::Header | LineKind::Separator | LineKind::Text))
+                .cloned().collect();
+
+            pieces.push(Piece::Boxed { title, content: trim_blank(content) });
+            pieces.extend(lines.iter().filter(|l| l.kind == LineKind::Text).map(Piece::Plain));
+        }
+        // категория от заголовка до пустой строки
+        BoxScope::Category => {
+            let mut current: Option<(&InfoLine, Vec<InfoLine>)> = None;
+
+            for line in lines {
+                match line.kind {
+                    LineKind::Title => {
+                        if let Some((title, content)) = current.take() {
+                            pieces.push(Piece::Boxed { title: Some(title), content });
+                        }
+                        current = Some((line, vec![]));
+                    }
+                    LineKind::Field if current.is_some() => {
+                        if let Some((_, content)) = current.as_mut() { content.push(line.clone()); }
+                    }
+                    LineKind::Blank if current.is_some() => {
+                        if let Some((title, content)) = current.take() {
+                            pieces.push(Piece::Boxed { title: Some(title), content });
+                        }
+                    }
+                    _ => pieces.push(Piece::Plain(line)),
+                }
+            }
+            if let Some((title, content)) = current {
+                pieces.push(Piece::Boxed { title: Some(title), content });
+            }
+        }
+    }
+
+    pieces
+}
+
+fn top_line(border: &Border, title: Option<&str>, inner: usize, color: &str) -> String {
+    let paint = |s: &str| colorize(s, color).to_string();
+
+    match title {
+        // "╭─ Title ───╮": перед заголовком "─ ", после пробел
+        Some(title) if inner >= 2 => {
+            let title = ellipsize(title, inner - 1);
+            let fill = inner - 1 - unicode_str_width(&title);
+            format!("{}{} {} {}{}",
+                paint(border.top_left), paint(border.horizontal), title,
+                paint(&border.horizontal.repeat(fill)), paint(border.top_right))
+        }
+        _ => format!("{}{}{}",
+            paint(border.top_left), paint(&border.horizontal.repeat(inner + 2)), paint(border.top_right)),
+    }
+}
+
+// room - сколько колонок есть под инфу вместе с рамкой, None - не ограничено
+pub fn frame_info(lines: &[InfoLine], cfg: &LayoutConfig, room: Option<usize>, color: &str) -> Vec<String> {
+    let border = border(cfg.border);
+    let pieces = split(lines, cfg.box_scope);
+
+    // все рамки одной ширины - так ровнее
+    let natural = pieces.iter()
+        .flat_map(|p| match p {
+            Piece::Plain(_) => vec![],
+            Piece::Boxed { title, content } => content.iter()
+                .map(|l| unicode_str_width(&l.text))
+                .chain(title.map(|t| unicode_str_width(&t.text) + 2))
+                .collect(),
+        })
+        .max().unwrap_or(0);
+    let inner = room.map_or(natural, |room| natural.min(room.saturating_sub(FRAME_WIDTH)));
+
+    let vertical = colorize(border.vertical, color).to_string();
+    let mut out = Vec::new();
+
+    for piece in pieces {
+        match piece {
+            Piece::Plain(line) => out.extend(fit_info(std::slice::from_ref(line), room, cfg.overflow)),
+            Piece::Boxed { title, content } => {
+                out.push(top_line(border, title.map(|t| t.text.as_str()), inner, color));
+                for line in fit_info(&content, Some(inner), cfg.overflow) {
+                    out.push(format!("{} {} {}", vertical, pad_to_width(&line, inner), vertical));
+                }
+                out.push(format!("{}{}{}",
+                    colorize(border.bottom_left, color),
+                    colorize(&border.horizontal.repeat(inner + 2), color),
+                    colorize(border.bottom_right, color)));
+            }
+        }
+    }
+
+    out
+}
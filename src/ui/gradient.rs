@@ -0,0 +1,89 @@
+use crate::config::{GradientConfig, GradientMode};
+use crate::ui::colors::{Rgb, parse_rgb, supports_truecolor, colorize, colorize_rgb};
+use colored::ColoredString;
+
+// радуга для 16 цветов - полосами, как у lolcat без truecolor
+const RAINBOW_FALLBACK: &[&str] = &[
+    "bright_red", "bright_yellow", "bright_green",
+    "bright_cyan", "bright_blue", "bright_magenta",
+];
+
+pub struct Gradient<'a> {
+    cfg:   &'a GradientConfig,
+    stops: Vec<Rgb>,
+    width:  usize,
+    height: usize,
+}
+
+impl<'a> Gradient<'a> {
+    pub fn new(cfg: &'a GradientConfig, width: usize, height: usize) -> Self {
+        let stops = cfg.stops.iter().filter_map(|s| parse_rgb(s)).collect();
+        Self { cfg, stops, width, height }
+    }
+
+    // положение символа на градиенте, 0.0..=1.0
+    fn position(&self, x: usize, y: usize) -> f32 {
+        let fx = x as f32 / self.width.saturating_sub(1).max(1) as f32;
+        let fy = y as f32 / self.height.saturating_sub(1).max(1) as f32;
+
+        match self.cfg.mode {
+            GradientMode::Vertical   => fy,
+            GradientMode::Horizontal => fx,
+            GradientMode::Diagonal   => (fx + fy) / 2.0,
+            GradientMode::Rainbow    => {
+                let phase = self.cfg.frequency * (x as f32 / 2.0 + y as f32);
+                phase.rem_euclid(1.0)
+            }
+        }
+    }
+
+    fn rgb_at(&self, t: f32) -> Rgb {
+        if self.cfg.mode == GradientMode::Rainbow {
+            return rainbow(t);
+        }
+
+        match self.stops.len() {
+            0 => (255, 255, 255),
+            1 => self.stops[0],
+            n => {
+                let scaled = t.clamp(0.0, 1.0) * (n - 1) as f32;
+                let i = (scaled.floor() as usize).min(n - 2);
+                lerp(self.stops[i], self.stops[i + 1], scaled - i as f32)
+            }
+        }
+    }
+
+    pub fn paint(&self, text: &str, x: usize, y: usize) -> ColoredString {
+        let t = self.position(x, y);
+
+        if supports_truecolor() {
+            return colorize_rgb(text, self.rgb_at(t));
+        }
+
+        // 16 цветов: полосы из fallback, для радуги свои, иначе ближайший к truecolor
+        let bands: Vec<&str> = if !self.cfg.fallback.is_empty() {
+            self.cfg.fallback.iter().map(|s| s.as_str()).collect()
+        } else if self.cfg.mode == GradientMode::Rainbow {
+            RAINBOW_FALLBACK.to_vec()
+        } else {
+            return colorize_rgb(text, self.rgb_at(t));
+        };
+
+        let idx = ((t * bands.len() as f32) as usize).min(bands.len() - 1);
+        colorize(text, bands[idx])
+    }
+}
+
+fn lerp(a: Rgb, b: Rgb, t: f32) -> Rgb {
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    (mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+}
+
+// синусоиды со сдвигом на треть периода, та же формула что у lolcat
+fn rainbow(t: f32) -> Rgb {
+    use std::f32::consts::PI;
+
+    let phase   = t * 2.0 * PI;
+    let channel = |shift: f32| ((phase + shift).sin() * 127.0 + 128.0) as u8;
+    (channel(0.0), channel(2.0 * PI / 3.0), channel(4.0 * PI / 3.0))
+}
@@ -0,0 +1,134 @@
+use crate::config::{LogoAlign, LogoPosition, Overflow};
+use crate::utils::{pad_to_width, unicode_str_width, ellipsize, wrap_to_width};
+
+// что за строка - рамкам надо знать где заголовки и где кончается категория
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineKind {
+    Header,
+    Separator,
+    Title,
+    Field,
+    Blank,
+    Text,
+}
+
+// строка инфы; indent - где начинается значение, с него продолжаются перенесённые строки
+#[derive(Clone)]
+pub struct InfoLine {
+    pub text:   String,
+    pub indent: usize,
+    pub kind:   LineKind,
+}
+
+impl InfoLine {
+    pub fn new(text: String, kind: LineKind) -> Self {
+        Self { text, indent: 0, kind }
+    }
+
+    pub fn field(text: String, indent: usize) -> Self {
+        Self { text, indent, kind: LineKind::Field }
+    }
+
+    pub fn blank() -> Self {
+        Self::new(String::new(), LineKind::Blank)
+    }
+}
+
+impl From<String> for InfoLine {
+    fn from(text: String) -> Self {
+        Self::new(text, LineKind::Text)
+    }
+}
+
+// подгоняем инфу под ширину, None - ширина неизвестна (вывод в пайп)
+pub fn fit_info(lines: &[InfoLine], width: Option<usize>, overflow: Overflow) -> Vec<String> {
+    let Some(width) = width else {
+        return lines.iter().map(|l| l.text.clone()).collect();
+    };
+
+    lines.iter().flat_map(|line| match overflow {
+        Overflow::Truncate => vec![ellipsize(&line.text, width)],
+        Overflow::Wrap     => wrap_to_width(&line.text, width, line.indent),
+        Overflow::None     => vec![line.text.clone()],
+    }).collect()
+}
+
+pub struct Layout {
+    pub position: LogoPosition,
+    // поля вокруг всего вывода: (top, right, bottom, left)
+    pub padding:  (usize, usize, usize, usize),
+    pub gap:      usize,
+    pub align:    LogoAlign,
+}
+
+// готовый вывод и где в нём начинается лого (строка, колонка) - туда рисуется картинка
+pub struct Composed {
+    pub lines:  Vec<String>,
+    pub origin: (usize, usize),
+}
+
+fn offset(align: LogoAlign, height: usize, total: usize) -> usize {
+    match align {
+        LogoAlign::Top    => 0,
+        LogoAlign::Center => (total - height) / 2,
+        LogoAlign::Bottom => total - height,
+    }
+}
+
+fn line(lines: &[String], i: usize) -> &str {
+    lines.get(i).map(|s| s.as_str()).unwrap_or("")
+}
+
+// art - уже покрашенные строки ширины art_width, info - строки инфы
+pub fn compose(art: &[String], art_width: usize, info: &[String], layout: &Layout) -> Composed {
+    let (top, _, bottom, left) = layout.padding;
+    let indent = " ".repeat(left);
+    let info_width = info.iter().map(|l| unicode_str_width(l)).max().unwrap_or(0);
+
+    let (body, origin): (Vec<String>, (usize, usize)) = match layout.position {
+        _ if art.is_empty() => (info.to_vec(), (0, 0)),
+
+        LogoPosition::Left | LogoPosition::Right => {
+            let total = art.len().max(info.len());
+            let art_y = offset(layout.align, art.len(), total);
+            let info_y = offset(layout.align, info.len(), total);
+            let gap = " ".repeat(layout.gap);
+
+            let lines = (0..total).map(|i| {
+                let art_row = pad_to_width(i.checked_sub(art_y).map(|i| line(art, i)).unwrap_or(""), art_width);
+                let info_row = i.checked_sub(info_y).map(|i| line(info, i)).unwrap_or("");
+
+                if layout.position == LogoPosition::Left {
+                    format!("{}{}{}", art_row, gap, info_row)
+                } else {
+                    format!("{}{}{}", pad_to_width(info_row, info_width), gap, art_row)
+                }
+            }).collect();
+
+            let art_x = match layout.position {
+                LogoPosition::Left => 0,
+                _                  => info_width + layout.gap,
+            };
+            (lines, (art_y, art_x))
+        }
+
+        LogoPosition::Top | LogoPosition::Bottom => {
+            let gap = std::iter::repeat_n(String::new(), layout.gap);
+            let art_rows = art.iter().map(|l| pad_to_width(l, art_width));
+
+            if layout.position == LogoPosition::Top {
+                (art_rows.chain(gap).chain(info.iter().cloned()).collect(), (0, 0))
+            } else {
+                let art_y = info.len() + layout.gap;
+                (info.iter().cloned().chain(gap).chain(art_rows).collect(), (art_y, 0))
+            }
+        }
+    };
+
+    let lines = std::iter::repeat_n(String::new(), top)
+        .chain(body.into_iter().map(|l| format!("{}{}", indent, l)))
+        .chain(std::iter::repeat_n(String::new(), bottom))
+        .collect();
+
+    Composed { lines, origin: (origin.0 + top, origin.1 + left) }
+}
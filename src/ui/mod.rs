@@ -1,137 +1,425 @@
 pub mod colors;
+pub mod gradient;
+pub mod layout;
+pub mod frame;
+pub mod palette;
 
-use crate::config::{Config, InfoField};
+use crate::config::{Config, CustomField, FieldRef, InfoField, LayoutStyle, LogoPosition, LogoType, NarrowMode, Padding};
 use crate::data::SysData;
+use crate::data::custom::CustomValue;
 use crate::ui::colors::colorize;
-use crate::localization::{localize_category, localize_field, category_icon};
-use crate::config::defaults::default_fields;
-use crate::ascii::load_ascii_art;
-use crate::utils::{shell_exec, unicode_str_width, whoami_username, whoami_hostname};
+use crate::ui::gradient::Gradient;
+use crate::ui::layout::{compose, fit_info, InfoLine, Layout, LineKind};
+use crate::ui::palette::palette_rows;
+use crate::ui::frame::{frame_info, FRAME_WIDTH};
+use crate::localization::category_icon;
+use crate::ascii::{load_ascii_art, AsciiArt, Fit};
+use crate::graphics::{load_image_logo, ImageLogo};
+use crate::graphics::convert::image_to_ascii;
+use crate::utils::{
+    split_color_markers, strip_color_markers, ellipsize, truncate_to_width, pad_to_width,
+    unicode_str_width, terminal_size, whoami_username, whoami_hostname,
+};
+use crate::utils::unicode::char_width;
 use colored::*;
 
+// строка инфы до выравнивания: ширину меток знаем только когда собраны все
+enum Row {
+    Line(String, LineKind),
+    // painted - значение уже раскрашено (шаблоном, правилом colors или самой командой)
+    Field { label: String, value: String, painted: bool },
+}
+
 pub struct Renderer<'a> {
     config: &'a Config,
     data:   &'a SysData,
 
-    ascii_lines: Vec<String>,
+    info_lines: Vec<InfoLine>,
+    ascii:      AsciiArt,
+    image:      Option<ImageLogo>,
 }
 
+// как было до настроек: пустая строка сверху и снизу, пробел слева
+const DEFAULT_PADDING: Padding = Padding::Sides { top: 1, right: 0, bottom: 1, left: 1 };
+
 impl<'a> Renderer<'a> {
     pub fn new(config: &'a Config, data: &'a SysData) -> Self {
-        let ascii_lines = if config.ascii.enabled {
-            load_ascii_art(&config.ascii)
-        } else { vec![] };
+        let mut renderer = Self {
+            config, data,
+            info_lines: vec![],
+            ascii:      AsciiArt::empty(),
+            image:      None,
+        };
+
+        // инфу собираем первой - от её размеров зависит какой арт влезет
+        renderer.info_lines = renderer.build_info_lines();
+
+        match config.logo.kind {
+            LogoType::None  => return renderer,
+            LogoType::Ascii => {}
+            // не смогли картинку - откатываемся на ascii
+            LogoType::Image => match load_image_logo(&config.logo, renderer.info_lines.len()) {
+                Ok(Some(image)) => {
+                    // под картинку оставляем пустое место, сама она рисуется поверх
+                    renderer.ascii = AsciiArt::painted(vec![" ".repeat(image.cols); image.rows]);
+                    renderer.image = Some(image);
+                    return renderer;
+                }
+                Ok(None) => {}
+                Err(e)   => eprintln!("rushfetch: {}, using ascii logo", e),
+            },
+            LogoType::ImageAscii => match image_to_ascii(&config.logo, renderer.info_lines.len(), &renderer.art_fit()) {
+                Ok(lines) => {
+                    renderer.ascii = AsciiArt::painted(lines);
+                    return renderer;
+                }
+                Err(e) => eprintln!("rushfetch: {}, using ascii logo", e),
+            },
+        }
+
+        if config.ascii.enabled {
+            renderer.ascii = load_ascii_art(&config.ascii, &renderer.art_fit());
+        }
 
-        Self { config, data, ascii_lines }
+        renderer
     }
 
-    fn build_info_lines(&self) -> Vec<String> {
-        let lang = self.config.language;
+    fn layout(&self) -> Layout {
+        let logo = &self.config.logo;
+        let position = logo.position.or(self.ascii.position).unwrap_or_default();
+
+        Layout {
+            position,
+            padding: logo.padding.unwrap_or(DEFAULT_PADDING).sides(),
+            gap: logo.gap.unwrap_or(match position {
+                LogoPosition::Left | LogoPosition::Right => 3,
+                LogoPosition::Top  | LogoPosition::Bottom => 1,
+            }),
+            align: logo.align,
+        }
+    }
+
+    // высота инфо сбоку не ограничивает: по умолчанию лого остаётся полным, как раньше
+    fn art_fit(&self) -> Fit {
+        let info_width = self.info_width();
+        let info_height = self.info_lines.len();
+        let layout = self.layout();
+        let (top, right, bottom, left) = layout.padding;
+        let term = terminal_size();
+
+        match layout.position {
+            LogoPosition::Left | LogoPosition::Right => Fit {
+                max_width: term.as_ref()
+                    .map(|t| t.cols.saturating_sub(info_width + layout.gap + left + right + 1)),
+                max_height: term.as_ref().filter(|t| t.rows > 0).map(|t| t.rows),
+            },
+            // сверху/снизу лого делит с инфой высоту, а не ширину
+            LogoPosition::Top | LogoPosition::Bottom => Fit {
+                max_width: term.as_ref().map(|t| t.cols.saturating_sub(left + right + 1)),
+                max_height: term.as_ref()
+                    .filter(|t| t.rows > 0)
+                    .map(|t| t.rows.saturating_sub(info_height + layout.gap + top + bottom)),
+            },
+        }
+    }
+
+    // иконка из темы/конфига важнее встроенной
+    fn icon(&self, custom: Option<&str>, builtin: &str) -> String {
+        if !self.config.show_icons { return String::new(); }
+
+        match custom {
+            Some("")   => String::new(),
+            Some(icon) => format!("{} ", icon),
+            None       => builtin.to_string(),
+        }
+    }
+
+    fn info_width(&self) -> usize {
+        self.info_lines.iter()
+            .map(|l| unicode_str_width(&l.text))
+            .max().unwrap_or(0)
+    }
+
+    // ширина колонки меток - по самой длинной метке, в рамках min/max из конфига
+    fn label_width(&self, rows: &[Row]) -> usize {
+        let labels = &self.config.labels;
+        let longest = rows.iter()
+            .filter_map(|row| match row {
+                Row::Field { label, .. } => Some(unicode_str_width(label)),
+                Row::Line(..)            => None,
+            })
+            .max().unwrap_or(0);
+
+        let width = (longest + 2).max(labels.min_width);
+        labels.max_width.map_or(width, |max| width.min(max))
+    }
+
+    // "OS" -> "OS ..........", format! тут не годится - он считает символы, а не ширину
+    fn format_label(&self, label: &str, width: usize) -> String {
+        let filler = &self.config.labels.filler;
+        if filler.is_empty() { return label.to_string(); }
+
+        let label = ellipsize(label, width.saturating_sub(1));
+        let fill = width.saturating_sub(unicode_str_width(&label) + 1);
+        let fill = truncate_to_width(&filler.repeat(fill), fill);
+        pad_to_width(&format!("{} {}", label, fill), width)
+    }
+
+    fn build_info_lines(&self) -> Vec<InfoLine> {
         let theme = &self.config.theme;
-        let mut lines: Vec<String> = Vec::new();
+        let rows = self.collect_rows();
+        let width = self.label_width(&rows);
+        let separator = &self.config.labels.separator;
+
+        let mut lines: Vec<InfoLine> = Vec::new();
+        for row in rows {
+            let (label, value, painted) = match row {
+                Row::Line(text, kind) => {
+                    lines.push(InfoLine::new(text, kind));
+                    continue;
+                }
+                Row::Field { label, value, painted } => (label, value, painted),
+            };
+
+            let label = self.format_label(&label, width);
+            let indent = 2 + unicode_str_width(&label) + unicode_str_width(separator);
+            let paint = |line: &str| if painted { line.to_string() } else { colorize(line, &theme.text).bold().to_string() };
+
+            // многострочное значение: следующие строки под первой
+            let mut values = value.split('\n');
+            lines.push(InfoLine::field(format!(
+                "  {}{}{}",
+                colorize(&label, &theme.secondary),
+                colorize(separator, &theme.secondary),
+                paint(values.next().unwrap_or(""))
+            ), indent));
+            for line in values {
+                lines.push(InfoLine::field(format!("{}{}", " ".repeat(indent), paint(line)), indent));
+            }
+        }
+
+        // палитра последней, под всей инфой
+        let info_width = lines.iter().map(|l| unicode_str_width(&l.text)).max().unwrap_or(0);
+        let palette = palette_rows(&self.config.palette, info_width);
+        if !palette.is_empty() {
+            lines.extend(palette.into_iter().map(InfoLine::from));
+            lines.push(InfoLine::blank());
+        }
+
+        lines
+    }
+
+    // по шаблону из [format], если он есть. шаблоны уже проверены при загрузке конфига
+    fn field_value(&self, field: InfoField) -> Option<(String, bool)> {
+        let Some(template) = self.config.templates.get(&field) else {
+            return self.data.get(field).map(|v| (v, false));
+        };
+
+        let theme = &self.config.theme;
+        let plain = |s: &str| colorize(s, &theme.text).bold().to_string();
+        self.data.values(field).map(|values| (template.render(&values, &plain), true))
+    }
+
+    // команды уже отработали в SysData::collect, тут только их вывод
+    fn custom_row(&self, pick: impl Fn(&CustomField) -> bool) -> Option<Row> {
+        let idx = self.config.custom_fields.iter().position(pick)?;
+        let field = &self.config.custom_fields[idx];
+        let commands = &self.config.commands;
+        let placeholder = || field.placeholder.clone().unwrap_or_else(|| commands.placeholder.clone());
+
+        let value = match self.data.custom.get(idx)? {
+            CustomValue::Empty if field.hide_if_empty => return None,
+            CustomValue::Failed(_) | CustomValue::TimedOut if field.hide_if_failed => return None,
+            CustomValue::Value(value) | CustomValue::Failed(Some(value)) => value.clone(),
+            CustomValue::Empty | CustomValue::Failed(None) => placeholder(),
+            CustomValue::TimedOut => commands.timeout_placeholder.clone(),
+        };
+
+        let value = value.lines()
+            .take(field.max_lines.unwrap_or(usize::MAX))
+            .map(str::trim_end)
+            .collect::<Vec<_>>().join("\n");
+        let value = field.map.get(&value).cloned().unwrap_or(value);
+
+        let (value, painted) = match field.colors.iter().find(|rule| rule.matches(&value)) {
+            Some(rule) => (value.split('\n')
+                .map(|line| colorize(line, &rule.color).bold().to_string())
+                .collect::<Vec<_>>().join("\n"), true),
+            None => (value, field.keep_ansi),
+        };
+        Some(Row::Field { label: field.label.clone(), value, painted })
+    }
+
+    fn collect_rows(&self) -> Vec<Row> {
+        let locale = &self.config.locale;
+        let theme = &self.config.theme;
+        let mut rows: Vec<Row> = Vec::new();
 
         let username = whoami_username();
         let hostname = whoami_hostname();
         let header = format!("{}@{}", username, hostname);
-        let separator = "─".repeat(header.len());
+        let separator = "─".repeat(unicode_str_width(&header));
 
-        lines.push(format!(
+        rows.push(Row::Line(format!(
             "{}", colorize(&header, &theme.accent).bold()
-        ));
-        lines.push(format!(
+        ), LineKind::Header));
+        rows.push(Row::Line(format!(
             "{}", colorize(&separator, &theme.separator)
-        ));
-        lines.push(String::new());
+        ), LineKind::Separator));
+        rows.push(Row::Line(String::new(), LineKind::Blank));
 
         for category_cfg in &self.config.categories {
             if !category_cfg.enabled { continue; }
 
             let category = category_cfg.category;
-            let category_name = localize_category(category, lang);
-            let icon = if self.config.show_icons {
-                category_icon(category)
-            } else { "" };
+            let name = match (&category_cfg.name, category) {
+                (Some(name), _)     => name.as_str(),
+                (None, Some(cat))   => locale.category(cat),
+                (None, None)        => continue,
+            };
+            let icon = self.icon(
+                category_cfg.icon.as_deref().or(category.and_then(|c| self.config.icons.category(c))),
+                category.map(category_icon).unwrap_or(""),
+            );
+            let color = category_cfg.color.as_deref().unwrap_or(&theme.primary);
 
-            lines.push(format!(
+            rows.push(Row::Line(format!(
                 "{}{}",
-                colorize(icon, &theme.primary),
-                colorize(category_name, &theme.primary).bold()
-            ));
-
-            let fields: &[InfoField] = if category_cfg.fields.is_empty() {
-                default_fields(category)
-            } else {
-                &category_cfg.fields
-            };
+                colorize(&icon, color),
+                colorize(name, color).bold()
+            ), LineKind::Title));
 
-            for &field in fields {
-                if let Some(value) = self.data.get(field) {
-                    let label = localize_field(field, lang);
-                    let dot_label = format!("{:.<14}", format!("{} ", label));
-                    lines.push(format!(
-                        "  {} {}",
-                        colorize(&dot_label, &theme.secondary),
-                        colorize(&value, &theme.text).bold()
-                    ));
+            for field in &category_cfg.field_list() {
+                match field {
+                    FieldRef::Builtin(field) => {
+                        if let Some((value, painted)) = self.field_value(*field) {
+                            let label = locale.field(*field).to_string();
+                            rows.push(Row::Field { label, value, painted });
+                        }
+                    }
+                    FieldRef::Custom(name) => {
+                        if let Some(row) = self.custom_row(|c| c.matches(name)) {
+                            rows.push(row);
+                        }
+                    }
                 }
             }
-            lines.push(String::new());
+            rows.push(Row::Line(String::new(), LineKind::Blank));
         }
 
-        // Кастомные поля
-        if !self.config.custom_fields.is_empty() {
-            let icon = if self.config.show_icons { "󰆾 " } else { "" };
-            lines.push(format!(
+        // Кастомные поля, которые не попали ни в одну категорию
+        let placed = |custom: &CustomField| self.config.categories.iter()
+            .flat_map(|c| &c.fields)
+            .any(|f| matches!(f, FieldRef::Custom(name) if custom.matches(name)));
+        let unplaced: Vec<&CustomField> = self.config.custom_fields.iter()
+            .filter(|c| !placed(c))
+            .collect();
+
+        if !unplaced.is_empty() {
+            let icon = self.icon(self.config.icons.custom.as_deref(), "󰆾 ");
+            rows.push(Row::Line(format!(
                 "{}{}",
-                colorize(icon, &theme.primary),
-                colorize("Custom", &theme.primary).bold()
-            ));
-
-            for custom in &self.config.custom_fields {
-                let value = shell_exec(&custom.command).unwrap_or_else(|| "N/A".to_string());
-                let dot_label = format!("{:.<16}", format!("{} ", custom.label));
-                lines.push(format!(
-                    "  {} {}",
-                    colorize(&dot_label, &theme.secondary),
-                    colorize(&value, &theme.text).bold()
-                ));
+                colorize(&icon, &theme.primary),
+                colorize(locale.custom(), &theme.primary).bold()
+            ), LineKind::Title));
+
+            for custom in unplaced {
+                rows.extend(self.custom_row(|c| std::ptr::eq(c, custom)));
             }
-            lines.push(String::new());
+            rows.push(Row::Line(String::new(), LineKind::Blank));
         }
 
-        lines
+        rows
+    }
+
+    // каждый кусок между маркерами ${cN} красим своим цветом из палитры
+    fn paint_ascii_line(&self, line: &str) -> String {
+        split_color_markers(line).into_iter()
+            .map(|(idx, text)| colorize(text, self.ascii.color(idx)).bold().to_string())
+            .collect()
+    }
+
+    fn paint_art_line(&self, line: &str, y: usize, gradient: Option<&Gradient>) -> String {
+        match gradient {
+            _ if self.ascii.painted => line.to_string(),
+            Some(g) => paint_gradient_line(line, y, g),
+            None    => self.paint_ascii_line(line),
+        }
+    }
+
+    // место под картинку уже напечатано пробелами - возвращаемся туда и рисуем поверх.
+    // так терминал не проскроллит посреди вывода
+    fn draw_image(&self, image: &ImageLogo, (row, col): (usize, usize), total: usize) {
+        let up = total - row;
+        print!("\x1b[{}A\x1b[{}G\x1b7{}\x1b8\x1b[{}B\r", up, col + 1, image.escape, up);
     }
 
+    // в узком терминале лого уходит наверх или пропадает, а инфа подгоняется под ширину
     pub fn render(&self) {
-        let info_lines = self.build_info_lines();
+        let cfg = &self.config.layout;
+        let mut layout = self.layout();
+        let (_, right, _, left) = layout.padding;
+        let cols = terminal_size().map(|t| t.cols.saturating_sub(left + right));
+        let art_width = self.ascii.width();
+        let mut show_art = !self.ascii.is_empty();
 
-        if self.ascii_lines.is_empty() {
-            println!();
-            for line in &info_lines {
-                println!(" {}", line);
+        let side_by_side = |l: &Layout| matches!(l.position, LogoPosition::Left | LogoPosition::Right);
+        if let Some(cols) = cols && show_art && side_by_side(&layout) && cfg.narrow != NarrowMode::Keep {
+            let room = cols.saturating_sub(art_width + layout.gap);
+            let framed = if cfg.style == LayoutStyle::Box { FRAME_WIDTH } else { 0 };
+            if room < (self.info_width() + framed).min(cfg.min_info_width) {
+                match cfg.narrow {
+                    NarrowMode::Stack if art_width <= cols => {
+                        layout.position = LogoPosition::Top;
+                        layout.gap = 1;
+                    }
+                    _ => show_art = false,
+                }
             }
-            println!();
-            return;
         }
 
-        // Считаем реальную ширину ASCII art
-        let actual_ascii_width = self.ascii_lines.iter()
-            .map(|l| unicode_str_width(l))
-            .max().unwrap_or(0);
-        
-        let ascii_w = actual_ascii_width + 1; // 1 на отступ
-        let gap = "  ";
-        let total = self.ascii_lines.len().max(info_lines.len());
-
-        println!();
-        for i in 0..total {
-            let art = self.ascii_lines.get(i).map(|s| s.as_str()).unwrap_or("");
-            let info = info_lines.get(i).map(|s| s.as_str()).unwrap_or("");
-
-            let colored_art = colorize(art, &self.config.ascii.color).bold();
-            print!(" {:<width$}{}", colored_art, gap, width = ascii_w);
-            println!("{}", info);
+        let info_room = match cols {
+            Some(cols) if show_art && side_by_side(&layout) => Some(cols.saturating_sub(art_width + layout.gap)),
+            cols => cols,
+        };
+        let info = match cfg.style {
+            LayoutStyle::Plain => fit_info(&self.info_lines, info_room, cfg.overflow),
+            LayoutStyle::Box   => frame_info(&self.info_lines, cfg, info_room, &self.config.theme.separator),
+        };
+
+        let gradient = self.config.ascii.gradient.as_ref()
+            .map(|g| Gradient::new(g, art_width, self.ascii.lines.len()));
+
+        let art: Vec<String> = if show_art {
+            self.ascii.lines.iter().enumerate()
+                .map(|(y, line)| self.paint_art_line(line, y, gradient.as_ref()))
+                .collect()
+        } else { vec![] };
+        let composed = compose(&art, art_width, &info, &layout);
+
+        for line in &composed.lines {
+            println!("{}", line);
+        }
+
+        if show_art && let Some(image) = &self.image {
+            self.draw_image(image, composed.origin, composed.lines.len());
+        }
+    }
+}
+
+// градиент считается для каждого символа отдельно, пробелы не красим
+fn paint_gradient_line(line: &str, y: usize, gradient: &Gradient) -> String {
+    let mut out = String::new();
+    let mut x = 0;
+
+    for c in strip_color_markers(line).chars() {
+        if c.is_whitespace() {
+            out.push(c);
+        } else {
+            out.push_str(&gradient.paint(c.encode_utf8(&mut [0; 4]), x, y).bold().to_string());
         }
-        println!();
+        x += char_width(c);
     }
+
+    out
 }
@@ -0,0 +1,36 @@
+use crate::config::{PaletteAlign, PaletteConfig};
+use crate::ui::colors::{ansi_name, colorize};
+use crate::utils::unicode_str_width;
+
+// "0-7" -> 0..=7, "4" -> 4..=4
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    let (from, to) = range.split_once('-').unwrap_or((range, range));
+    let (from, to) = (from.trim().parse().ok()?, to.trim().parse().ok()?);
+
+    (from <= to && to < 16).then_some((from, to))
+}
+
+pub fn palette_rows(cfg: &PaletteConfig, info_width: usize) -> Vec<String> {
+    if !cfg.enabled { return vec![]; }
+
+    let block = cfg.block.repeat(cfg.width.max(1));
+
+    cfg.rows.iter().filter_map(|range| {
+        let Some((from, to)) = parse_range(range) else {
+            eprintln!("rushfetch: bad palette row \"{}\", expected a range like \"0-7\" within 0..15", range);
+            return None;
+        };
+
+        let row: String = (from..=to)
+            .filter_map(ansi_name)
+            .map(|name| colorize(&block, name).to_string())
+            .collect();
+
+        let indent = match cfg.align {
+            PaletteAlign::Left   => 0,
+            PaletteAlign::Fields => 2,
+            PaletteAlign::Center => info_width.saturating_sub(unicode_str_width(&row)) / 2,
+        };
+        Some(format!("{}{}", " ".repeat(indent), row))
+    }).collect()
+}
@@ -1,20 +1,140 @@
 pub mod unicode;
 pub mod shell;
+pub mod term;
 
-pub use unicode::unicode_str_width;
-pub use shell::{shell_exec, whoami_username, whoami_hostname, shell_name};
+pub use unicode::{
+    unicode_str_width, split_color_markers, strip_color_markers,
+    strip_ansi, truncate_to_width, pad_to_width, ellipsize, wrap_to_width,
+};
+pub use term::{terminal_size, TermSize};
+pub use shell::{run_command, run_exec, CommandOutcome, whoami_username, whoami_hostname, shell_name};
 
 use std::borrow::Cow;
+use std::time::Duration;
+use std::env;
 use std::fs;
 use std::io::{self, BufRead};
+use std::path::PathBuf;
 
 pub fn read_file_cow(path: &str) -> Option<Cow<'static, str>> {
     fs::read_to_string(path)
-        .ok().map(|s| Cow::Owned(s))
+        .ok().map(Cow::Owned)
 }
 
 pub fn read_lines_from(path: &str) -> Option<Vec<String>> {
     let file    = fs::File::open(path).ok()?;
     let reader  = io::BufReader::new(file);
-    Some(reader.lines().filter_map(|l| l.ok()).collect())
+    Some(reader.lines().map_while(Result::ok).collect())
+}
+
+// $XDG_CONFIG_HOME или ~/.config
+pub fn config_home() -> Option<PathBuf> {
+    env::var("XDG_CONFIG_HOME").ok()
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var("HOME").ok().map(|h| PathBuf::from(h).join(".config")))
+}
+
+// $XDG_CACHE_HOME или ~/.cache
+pub fn cache_home() -> Option<PathBuf> {
+    env::var("XDG_CACHE_HOME").ok()
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var("HOME").ok().map(|h| PathBuf::from(h).join(".cache")))
+}
+
+// "90", "30s", "5m", "1h30m", "2d"; голое число - секунды
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    if let Ok(secs) = s.parse::<u64>() { return Some(Duration::from_secs(secs)); }
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            'w' => 7 * 86400,
+            _   => return None,
+        };
+        // "99999999999999999d" не влезает в u64 - такой срок просто неверный
+        total = total.checked_add(number.parse::<u64>().ok()?.checked_mul(unit)?)?;
+        number.clear();
+    }
+
+    (number.is_empty() && !s.is_empty()).then(|| Duration::from_secs(total))
+}
+
+// шаблон с * и ?, как в шелле, без [...]
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let (p, t): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut pi, mut ti) = (0, 0);
+    // где была последняя * и сколько текста она уже съела
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        match p.get(pi) {
+            Some('*') => {
+                star = Some((pi, ti));
+                pi += 1;
+            }
+            Some(&c) if c == '?' || c == t[ti] => {
+                pi += 1;
+                ti += 1;
+            }
+            _ => match star {
+                Some((sp, st)) => {
+                    pi = sp + 1;
+                    ti = st + 1;
+                    star = Some((sp, st + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+// раскрывает ~ и $HOME в начале пути
+pub fn expand_home(path: &str) -> PathBuf {
+    let home = env::var("HOME").unwrap_or_default();
+
+    if path == "~" || path == "$HOME" {
+        return PathBuf::from(home);
+    }
+    if let Some(rest) = path.strip_prefix("~/").or_else(|| path.strip_prefix("$HOME/")) {
+        return PathBuf::from(home).join(rest);
+    }
+
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("2d"), Some(Duration::from_secs(2 * 86400)));
+        assert_eq!(parse_duration("1w"), Some(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("10m5"), None);
+    }
+
+    #[test]
+    fn parse_duration_overflow_is_none() {
+        assert_eq!(parse_duration("99999999999999999d"), None);
+        assert_eq!(parse_duration("18446744073709551615s1s"), None);
+        assert_eq!(parse_duration("99999999999999999999"), None);
+    }
 }
@@ -1,12 +1,110 @@
 use std::env;
 use std::fs;
+use std::io::{self, Read};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
 
-pub fn shell_exec(cmd: &str) -> Option<String> {
-    std::process::Command::new("sh")
-        .arg("-c").arg(cmd).output().ok()
-        .and_then(|o| String::from_utf8(o.stdout).ok())
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
+// как часто проверяем, не закончилась ли команда
+const POLL: Duration = Duration::from_millis(5);
+// сколько ещё ждём трубы после убийства группы, даже если дедлайн уже прошёл
+const GRACE: Duration = Duration::from_millis(50);
+
+pub enum CommandOutcome {
+    Done { status: ExitStatus, stdout: String, stderr: String },
+    TimedOut { stderr: String },
+    // процесс не запустился
+    Failed(String),
+}
+
+fn read_all(mut pipe: impl Read + Send + 'static) -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        let _ = tx.send(String::from_utf8_lossy(&buf).into_owned());
+    });
+    rx
+}
+
+// вся группа: sh и всё, что он запустил, в том числе `sleep 30 &`.
+// только пока child не собран wait: потом его pid, а с ним и группа, может достаться чужим
+fn kill_group(child: &Child) {
+    unsafe { libc::kill(-(child.id() as i32), libc::SIGKILL); }
+}
+
+// вышел ли процесс, не собирая его (WNOWAIT): зомби держит свой pid, группу можно убивать
+fn exited(child: &Child) -> io::Result<bool> {
+    let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+    let flags = libc::WEXITED | libc::WNOHANG | libc::WNOWAIT;
+    if unsafe { libc::waitid(libc::P_PID, child.id(), &mut info, flags) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // с WNOHANG, пока никто не вышел, si_pid остаётся нулём
+    Ok(unsafe { info.si_pid() } != 0)
+}
+
+// sh -c в своей группе процессов: по таймауту убиваем всю группу,
+// иначе `curl | jq` оставит висеть curl
+pub fn run_command(cmd: &str, timeout: Duration) -> CommandOutcome {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(cmd);
+    run(command, timeout)
+}
+
+// то же без shell: argv как есть
+pub fn run_exec(argv: &[String], timeout: Duration) -> CommandOutcome {
+    let Some((program, args)) = argv.split_first() else {
+        return CommandOutcome::Failed("empty exec".to_string());
+    };
+    let mut command = Command::new(program);
+    command.args(args);
+    run(command, timeout)
+}
+
+fn run(mut command: Command, timeout: Duration) -> CommandOutcome {
+    let child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e)    => return CommandOutcome::Failed(e.to_string()),
+    };
+
+    // трубы читаем сразу, иначе болтливая команда встанет на полном буфере
+    let stdout = child.stdout.take().map(read_all);
+    let stderr = child.stderr.take().map(read_all);
+    let deadline = Instant::now() + timeout;
+    // не дольше дедлайна: трубу может держать и процесс, ушедший из группы (setsid)
+    let collect = |rx: Option<Receiver<String>>| rx
+        .and_then(|rx| rx.recv_timeout(deadline.saturating_duration_since(Instant::now()).max(GRACE)).ok())
+        .unwrap_or_default();
+
+    loop {
+        match exited(&child) {
+            // sh вышел, но фоновые потомки держат трубы открытыми - сначала убираем их, потом собираем sh
+            Ok(true) => {
+                kill_group(&child);
+                return match child.wait() {
+                    Ok(status) => CommandOutcome::Done { status, stdout: collect(stdout), stderr: collect(stderr) },
+                    Err(e)     => CommandOutcome::Failed(e.to_string()),
+                };
+            }
+            Ok(false) if Instant::now() < deadline => thread::sleep(POLL),
+            Ok(false) => {
+                kill_group(&child);
+                let _ = child.wait();
+                return CommandOutcome::TimedOut { stderr: collect(stderr) };
+            }
+            // что с процессом - неизвестно, группу не трогаем
+            Err(e) => return CommandOutcome::Failed(e.to_string()),
+        }
+    }
 }
 
 pub fn whoami_username() -> String {
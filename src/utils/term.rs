@@ -0,0 +1,37 @@
+use std::env;
+
+pub struct TermSize {
+    pub cols: usize,
+    pub rows: usize,
+    // размер окна в пикселях, многие терминалы отдают 0
+    pub width_px:  usize,
+    pub height_px: usize,
+}
+
+fn ioctl_size(fd: i32) -> Option<TermSize> {
+    let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+
+    unsafe {
+        if libc::ioctl(fd, libc::TIOCGWINSZ, &mut ws) != 0 || ws.ws_col == 0 {
+            return None;
+        }
+    }
+
+    Some(TermSize {
+        cols:      ws.ws_col as usize,
+        rows:      ws.ws_row as usize,
+        width_px:  ws.ws_xpixel as usize,
+        height_px: ws.ws_ypixel as usize,
+    })
+}
+
+// TIOCGWINSZ на stdout/stderr/stdin, если вывод в пайп - $COLUMNS и $LINES
+pub fn terminal_size() -> Option<TermSize> {
+    [libc::STDOUT_FILENO, libc::STDERR_FILENO, libc::STDIN_FILENO].into_iter()
+        .find_map(ioctl_size)
+        .or_else(|| {
+            let cols = env::var("COLUMNS").ok()?.parse().ok()?;
+            let rows = env::var("LINES").ok().and_then(|r| r.parse().ok()).unwrap_or(0);
+            Some(TermSize { cols, rows, width_px: 0, height_px: 0 })
+        })
+}
@@ -1,20 +1,243 @@
 
+// маркеры цвета ${c1}..${c9} в артах (как в неофетче) - на экране их не видно
+pub fn color_marker(s: &str) -> Option<(usize, usize)> {
+    let b = s.as_bytes();
+    if b.len() >= 5 && b.starts_with(b"${c") && (b'1'..=b'9').contains(&b[3]) && b[4] == b'}' {
+        Some(((b[3] - b'1') as usize, 5))
+    } else { None }
+}
+
+// длина ANSI escape последовательности в начале строки (CSI и OSC)
+pub fn ansi_escape(s: &str) -> Option<usize> {
+    let b = s.as_bytes();
+    if b.len() < 2 || b[0] != 0x1b { return None; }
+
+    match b[1] {
+        b'[' => b[2..].iter()
+            .position(|c| (0x40..=0x7e).contains(c))
+            .map(|p| p + 3),
+        b']' => (2..b.len()).find_map(|i| match b[i] {
+            0x07                                   => Some(i + 1),
+            0x1b if b.get(i + 1) == Some(&b'\\')   => Some(i + 2),
+            _ => None,
+        }),
+        // ESC и любой символ; он может быть и многобайтным - режем по границе символа
+        _ => s[1..].chars().next().map(|c| 1 + c.len_utf8()),
+    }
+}
+
+// режем строку на куски одного цвета: (индекс в палитре, текст)
+pub fn split_color_markers(s: &str) -> Vec<(usize, &str)> {
+    let mut parts = Vec::new();
+    let mut color = 0;
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < s.len() {
+        if let Some((idx, len)) = color_marker(&s[i..]) {
+            if start < i { parts.push((color, &s[start..i])); }
+            color = idx;
+            i += len;
+            start = i;
+        } else {
+            i += s[i..].chars().next().map(char::len_utf8).unwrap_or(1);
+        }
+    }
+    if start < s.len() { parts.push((color, &s[start..])); }
+
+    parts
+}
+
+pub fn last_color_marker(s: &str) -> Option<usize> {
+    s.match_indices("${c")
+        .filter_map(|(i, _)| color_marker(&s[i..]))
+        .map(|(idx, _)| idx)
+        .last()
+}
+
+pub fn strip_color_markers(s: &str) -> String {
+    split_color_markers(s).into_iter().map(|(_, text)| text).collect()
+}
+
+pub fn strip_ansi(s: &str) -> String {
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < s.len() {
+        if let Some(len) = ansi_escape(&s[i..]) {
+            i += len;
+            continue;
+        }
+        let Some(c) = s[i..].chars().next() else { break };
+        out.push(c);
+        i += c.len_utf8();
+    }
+
+    out
+}
+
 // чтобы не тащить целый лишний крейт можно воспользоватся таким простым трюком
+pub fn char_width(c: char) -> usize {
+    let code = c as u32;
+    // асции ширина 1 символ
+    if code <= 0x7F { 1 }
+    // китайские/корейские/японские иероглифы и другие жирные символы - 2 ширины  
+    else if 
+        (0x1100..=0x115F).contains(&code) ||
+        (0x2E80..=0x2EFF).contains(&code) ||
+        (0x3000..=0x30FF).contains(&code) ||
+        (0x4E00..=0x9FFF).contains(&code) ||
+        (0xAC00..=0xD7AF).contains(&code) ||
+        (0xF900..=0xFAFF).contains(&code) ||
+        (0xFF00..=0xFFEF).contains(&code) { 2 }
+    // Эмодзи - 2 ширины - блять, кто использует эмодзи в артах терминала. я таких не знаю. 
+    else { 1 } // для всех остальных в 99% будет верно
+}
+
+// маркеры и ANSI коды места на экране не занимают
+fn invisible(s: &str) -> Option<usize> {
+    color_marker(s).map(|(_, len)| len).or_else(|| ansi_escape(s))
+}
+
 pub fn unicode_str_width(s: &str) -> usize {
-    s.chars().map(|c| {
-        let code = c as u32;
-        // асции ширина 1 символ
-        if code <= 0x7F { 1 }
-        // китайские/корейские/японские иероглифы и другие жирные символы - 2 ширины  
-        else if 
-            (0x1100..=0x115F).contains(&code) ||
-            (0x2E80..=0x2EFF).contains(&code) ||
-            (0x3000..=0x30FF).contains(&code) ||
-            (0x4E00..=0x9FFF).contains(&code) ||
-            (0xAC00..=0xD7AF).contains(&code) ||
-            (0xF900..=0xFAFF).contains(&code) ||
-            (0xFF00..=0xFFEF).contains(&code) { 2 }
-        // Эмодзи - 2 ширины - блять, кто использует эмодзи в артах терминала. я таких не знаю. 
-        else { 1 } // для всех остальных в 99% будет верно
-    }).sum()
+    let mut width = 0;
+    let mut i = 0;
+
+    while i < s.len() {
+        if let Some(len) = invisible(&s[i..]) {
+            i += len;
+            continue;
+        }
+        let Some(c) = s[i..].chars().next() else { break };
+        width += char_width(c);
+        i += c.len_utf8();
+    }
+
+    width
+}
+
+// обрезает до ширины, маркеры и escape коды при этом сохраняются
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    let mut result = String::new();
+    let mut current = 0;
+    let mut i = 0;
+
+    while i < s.len() {
+        if let Some(len) = invisible(&s[i..]) {
+            result.push_str(&s[i..i + len]);
+            i += len;
+            continue;
+        }
+        let Some(c) = s[i..].chars().next() else { break };
+        let w = char_width(c);
+        if current + w > width { break; }
+
+        result.push(c);
+        current += w;
+        i += c.len_utf8();
+    }
+
+    result
+}
+
+// добивает пробелами до ширины, длинное обрезает
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let w = unicode_str_width(s);
+    if w < width {
+        format!("{}{}", s, " ".repeat(width - w))
+    } else if w > width {
+        truncate_to_width(s, width)
+    } else { s.to_string() }
+}
+
+// обрезает с многоточием на конце
+pub fn ellipsize(s: &str, width: usize) -> String {
+    if unicode_str_width(s) <= width { return s.to_string(); }
+    if width == 0 { return String::new(); }
+
+    let cut = truncate_to_width(s, width - 1);
+    if cut.contains('\x1b') { format!("{}…\x1b[0m", cut) } else { format!("{}…", cut) }
+}
+
+fn is_reset(esc: &str) -> bool {
+    esc == "\x1b[0m" || esc == "\x1b[m"
+}
+
+// перенос по словам, продолжение с отступом indent. цвет, открытый до переноса,
+// закрывается в конце строки и открывается заново на следующей
+pub fn wrap_to_width(s: &str, width: usize, indent: usize) -> Vec<String> {
+    if width == 0 || unicode_str_width(s) <= width { return vec![s.to_string()]; }
+    // отступ почти во всю ширину - толку от него нет
+    let indent = if indent + 8 > width { 0 } else { indent };
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_w = 0;
+    // с какого байта в line начинается текст (после отступа)
+    let mut line_start = 0;
+    let mut active = String::new();
+    // последний пробел в строке: (байт, ширина вместе с ним, цвет на тот момент)
+    let mut space: Option<(usize, usize, String)> = None;
+    let mut i = 0;
+
+    while i < s.len() {
+        if let Some(len) = invisible(&s[i..]) {
+            let esc = &s[i..i + len];
+            line.push_str(esc);
+            if is_reset(esc) { active.clear(); } else if esc.ends_with('m') { active.push_str(esc); }
+            i += len;
+            continue;
+        }
+        let Some(c) = s[i..].chars().next() else { break };
+        let w = char_width(c);
+        i += c.len_utf8();
+
+        if line_w + w > width {
+            let (head, tail, tail_w, color) = match space.take() {
+                Some((at, at_w, color)) => (line[..at].to_string(), line[at + 1..].to_string(), line_w - at_w, color),
+                None => (line.clone(), String::new(), 0, active.clone()),
+            };
+            lines.push(if head.contains('\x1b') { format!("{}\x1b[0m", head) } else { head });
+
+            line = format!("{}{}", " ".repeat(indent), color);
+            line_start = line.len();
+            line.push_str(&tail);
+            line_w = indent + tail_w;
+
+            if c == ' ' && line.len() == line_start { continue; }
+        }
+
+        if c == ' ' && line.len() > line_start {
+            space = Some((line.len(), line_w + w, active.clone()));
+        }
+        line.push(c);
+        line_w += w;
+    }
+    lines.push(line);
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_before_multibyte_char_ends_on_char_boundary() {
+        let s = "\x1b\u{e9}x";
+        assert_eq!(ansi_escape(s), Some(3));
+        assert_eq!(strip_ansi(s), "x");
+        assert_eq!(unicode_str_width(s), 1);
+        assert_eq!(truncate_to_width(s, 1), s);
+        assert_eq!(wrap_to_width(s, 10, 0), [s]);
+    }
+
+    #[test]
+    fn lone_trailing_escape_is_kept_as_char() {
+        let s = "ab\x1b";
+        assert_eq!(ansi_escape("\x1b"), None);
+        assert_eq!(strip_ansi(s), s);
+        assert_eq!(truncate_to_width(s, 5), s);
+        assert_eq!(wrap_to_width("abc def\x1b", 4, 0).len(), 2);
+    }
 }